@@ -6,27 +6,102 @@ extern crate futures;
 extern crate grep;
 extern crate linkify;
 extern crate num_cpus;
+extern crate regex;
 extern crate reqwest;
 extern crate spinners;
 extern crate term;
 
 use clap::{Arg, Command};
+use regex::Regex;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use urlsup::finder::Finder;
-use urlsup::validator::Validator;
-use urlsup::{UrlsUp, UrlsUpOptions};
+use urlsup::format;
+use urlsup::ignore_file::{self, IGNORE_FILE};
+use urlsup::sink::ResultSink;
+use urlsup::validator::{self, sort_results, ValidationResult, Validator};
+use urlsup::{HeaderRule, Report, UrlLocation, UrlsUp, UrlsUpOptions};
 
+use std::collections::HashMap;
+use std::env;
 use std::ffi::OsStr;
-use std::path::Path;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
+use tokio::sync::watch;
 
 const OPT_FILES: &str = "FILES";
 const OPT_WHITE_LIST: &str = "white-list";
 const OPT_TIMEOUT: &str = "timeout";
 const OPT_ALLOW: &str = "allow";
 const OPT_THREADS: &str = "threads";
+const OPT_CONCURRENCY_PER_HOST: &str = "concurrency-per-host";
 const OPT_ALLOW_TIMEOUT: &str = "allow-timeout";
+const OPT_CONNECT_TIMEOUT: &str = "connect-timeout";
+const OPT_WEBHOOK_URL: &str = "webhook-url";
+const OPT_WEBHOOK_MAX_URLS: &str = "webhook-max-urls";
+const OPT_NO_INLINE_IGNORES: &str = "no-inline-ignores";
+const OPT_HOST_TIMEOUTS: &str = "host-timeouts";
+const OPT_MAX_FILE_SIZE: &str = "max-file-size";
+const OPT_WHITE_LIST_PATTERN: &str = "white-list-pattern";
+const OPT_MAX_FAILURES: &str = "max-failures";
+const OPT_MAX_REQUESTS: &str = "max-requests";
+const OPT_OUTPUT: &str = "output";
+const OPT_FORMAT: &str = "format";
+const OPT_MAX_RUNTIME: &str = "max-runtime";
+const OPT_ALLOW_TIMEOUT_HOSTS: &str = "allow-timeout-hosts";
+const OPT_COOKIES: &str = "cookies";
+const OPT_FAIL_ON_OFFSITE_REDIRECT: &str = "fail-on-offsite-redirect";
+const OPT_ALLOWED_REDIRECT_HOSTS: &str = "allowed-redirect-hosts";
+const OPT_ONLY_STATUS: &str = "only-status";
+const OPT_PRE_RESOLVE_HOSTS: &str = "pre-resolve-hosts";
+const OPT_SHOW_ALL: &str = "show-all";
+const OPT_SPREAD_OVER: &str = "spread-over";
+const OPT_STEP_SUMMARY: &str = "step-summary";
+const OPT_NO_REDIRECTS_OK: &str = "no-redirects-ok";
+const OPT_PREFLIGHT: &str = "preflight";
+const OPT_SKIP_UNREACHABLE_HOSTS: &str = "skip-unreachable-hosts";
+const OPT_HEADER_RULE: &str = "header-rule";
+const OPT_NO_FOLLOW_REDIRECTS: &str = "no-follow-redirects";
+const OPT_SORT_BY: &str = "sort-by";
+const OPT_MIN_TLS_VERSION: &str = "min-tls-version";
+const OPT_UNIQUE_PER_FILE: &str = "unique-per-file";
+const OPT_URL_REWRITE: &str = "url-rewrite";
+const OPT_STATS: &str = "stats";
+const OPT_FAIL_IF_EMPTY: &str = "fail-if-empty";
+const OPT_HOST_OVERRIDES: &str = "host-overrides";
+const OPT_SUGGEST_FIXES: &str = "suggest-fixes";
+const OPT_USER_AGENT: &str = "user-agent";
+const OPT_FRONTMATTER_ONLY: &str = "frontmatter-only";
+const OPT_REPORT_UNREACHABLE_ONLY: &str = "report-unreachable-only";
+const OPT_RESPECT_ROBOTS: &str = "respect-robots";
+const OPT_URLS_FROM: &str = "urls-from";
+const OPT_GROUP_BY_URL: &str = "group-by-url";
+const OPT_SHOW_CONTEXT: &str = "show-context";
+const OPT_CHECK_DUPLICATES: &str = "check-duplicates";
+const OPT_POOL_MAX_IDLE_PER_HOST: &str = "pool-max-idle-per-host";
+const OPT_POOL_IDLE_TIMEOUT: &str = "pool-idle-timeout";
+const OPT_TCP_KEEPALIVE: &str = "tcp-keepalive";
+const OPT_REPORT_SKIPPED_SCHEMES: &str = "report-skipped-schemes";
+const OPT_RESUME: &str = "resume";
+const OPT_WARN_ON_EMBEDDED_CREDENTIALS: &str = "warn-on-embedded-credentials";
+const OPT_REPORT_REDIRECTS: &str = "report-redirects";
+const OPT_DELAY_BETWEEN_FILES: &str = "delay-between-files";
+const OPT_CHECK_BOTH_SCHEMES: &str = "check-both-schemes";
+const OPT_MAX_BROKEN: &str = "max-broken";
+const OPT_COMMENTS_ONLY: &str = "comments-only";
+const OPT_SINCE_LAST_RUN: &str = "since-last-run";
+
+const CMD_CHECK: &str = "check";
+const OPT_URL: &str = "URL";
+
+// Set by GitHub Actions to the file with the job summary
+const GITHUB_STEP_SUMMARY: &str = "GITHUB_STEP_SUMMARY";
 
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_WEBHOOK_MAX_URLS: usize = 50;
+const DEFAULT_MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
 
 #[tokio::main]
 async fn main() {
@@ -35,7 +110,7 @@ async fn main() {
         .validator_os(exists_on_filesystem)
         .takes_value(true)
         .multiple_values(true)
-        .required(true)
+        .required_unless_present(OPT_URLS_FROM)
         .index(1);
 
     let opt_white_list = Arg::new(OPT_WHITE_LIST)
@@ -55,7 +130,7 @@ async fn main() {
         .required(false);
 
     let opt_allow = Arg::new(OPT_ALLOW)
-        .help("Comma separated status code errors to allow")
+        .help("Comma separated status code errors to allow, or ranges like 400-410 or 4xx")
         .short('a')
         .long(OPT_ALLOW)
         .value_name("status codes")
@@ -63,18 +138,403 @@ async fn main() {
         .required(false);
 
     let opt_threads = Arg::new(OPT_THREADS)
-        .help("Thread count for making requests (default: CPU core count)")
+        .help("Thread count for making requests, 0 for one per URL up to 512 (default: CPU core count)")
         .long(OPT_THREADS)
+        .alias("concurrency")
         .value_name("thread count")
         .takes_value(true)
         .required(false);
 
+    let opt_concurrency_per_host = Arg::new(OPT_CONCURRENCY_PER_HOST)
+        .help("Max number of requests to the same host at the same time, at most the thread count")
+        .long(OPT_CONCURRENCY_PER_HOST)
+        .value_name("count")
+        .takes_value(true)
+        .required(false);
+
     let opt_allow_timeout = Arg::new(OPT_ALLOW_TIMEOUT)
         .help("URLs that time out are allowed")
         .long(OPT_ALLOW_TIMEOUT)
         .takes_value(false)
         .required(false);
 
+    let opt_connect_timeout = Arg::new(OPT_CONNECT_TIMEOUT)
+        .help("Timeout in seconds for establishing a connection (default: same as timeout)")
+        .long(OPT_CONNECT_TIMEOUT)
+        .value_name("seconds")
+        .takes_value(true)
+        .required(false);
+
+    let opt_webhook_url = Arg::new(OPT_WEBHOOK_URL)
+        .help("URL to POST a JSON summary to when issues are found")
+        .long(OPT_WEBHOOK_URL)
+        .value_name("url")
+        .takes_value(true)
+        .required(false);
+
+    let opt_webhook_max_urls = Arg::new(OPT_WEBHOOK_MAX_URLS)
+        .help("Max number of broken URLs to include in the webhook summary (default: 50)")
+        .long(OPT_WEBHOOK_MAX_URLS)
+        .value_name("count")
+        .takes_value(true)
+        .required(false);
+
+    let opt_no_inline_ignores = Arg::new(OPT_NO_INLINE_IGNORES)
+        .help("Check URLs marked with <!-- urlsup-ignore --> or <!-- urlsup-ignore-next-line -->")
+        .long(OPT_NO_INLINE_IGNORES)
+        .takes_value(false)
+        .required(false);
+
+    let opt_host_timeouts = Arg::new(OPT_HOST_TIMEOUTS)
+        .help("Comma separated host=seconds timeouts overriding the timeout for hosts and their subdomains")
+        .long(OPT_HOST_TIMEOUTS)
+        .value_name("host=seconds")
+        .takes_value(true)
+        .required(false);
+
+    let opt_max_file_size = Arg::new(OPT_MAX_FILE_SIZE)
+        .help("Skip files larger than this many bytes (default: 10485760)")
+        .long(OPT_MAX_FILE_SIZE)
+        .value_name("bytes")
+        .takes_value(true)
+        .required(false);
+
+    let opt_white_list_pattern = Arg::new(OPT_WHITE_LIST_PATTERN)
+        .help("Regex of URLs to allow being broken, can be used multiple times")
+        .long(OPT_WHITE_LIST_PATTERN)
+        .value_name("regex")
+        .takes_value(true)
+        .multiple_occurrences(true)
+        .required(false);
+
+    let opt_max_failures = Arg::new(OPT_MAX_FAILURES)
+        .help("Stop checking URLs after this many issues")
+        .long(OPT_MAX_FAILURES)
+        .value_name("count")
+        .takes_value(true)
+        .required(false);
+
+    let opt_max_requests = Arg::new(OPT_MAX_REQUESTS)
+        .help("Stop checking URLs after this many requests and exit with status 3")
+        .long(OPT_MAX_REQUESTS)
+        .value_name("count")
+        .takes_value(true)
+        .required(false);
+
+    let opt_output = Arg::new(OPT_OUTPUT)
+        .help("Write the results to a file instead of stdout")
+        .short('o')
+        .long(OPT_OUTPUT)
+        .value_name("path")
+        .takes_value(true)
+        .required(false);
+
+    let opt_format = Arg::new(OPT_FORMAT)
+        .help("Format of the results (default: text)")
+        .long(OPT_FORMAT)
+        .value_name("format")
+        .possible_values(["text", "markdown", "ndjson"])
+        .takes_value(true)
+        .required(false);
+
+    let opt_max_runtime = Arg::new(OPT_MAX_RUNTIME)
+        .help("Stop checking URLs after this many seconds and exit with status 2")
+        .long(OPT_MAX_RUNTIME)
+        .value_name("seconds")
+        .takes_value(true)
+        .required(false);
+
+    let opt_allow_timeout_hosts = Arg::new(OPT_ALLOW_TIMEOUT_HOSTS)
+        .help("Comma separated hosts whose URLs are allowed to time out")
+        .long(OPT_ALLOW_TIMEOUT_HOSTS)
+        .value_name("hosts")
+        .takes_value(true)
+        .required(false);
+
+    let opt_cookies = Arg::new(OPT_COOKIES)
+        .help("Keep cookies set by responses and send them with later requests")
+        .long(OPT_COOKIES)
+        .takes_value(false)
+        .required(false);
+
+    let opt_fail_on_offsite_redirect = Arg::new(OPT_FAIL_ON_OFFSITE_REDIRECT)
+        .help("URLs redirecting to a different host are issues")
+        .long(OPT_FAIL_ON_OFFSITE_REDIRECT)
+        .takes_value(false)
+        .required(false);
+
+    let opt_allowed_redirect_hosts = Arg::new(OPT_ALLOWED_REDIRECT_HOSTS)
+        .help("Comma separated hosts that URLs are allowed to redirect to")
+        .long(OPT_ALLOWED_REDIRECT_HOSTS)
+        .value_name("hosts")
+        .takes_value(true)
+        .required(false);
+
+    let opt_only_status = Arg::new(OPT_ONLY_STATUS)
+        .help(
+            "Comma separated status codes or ranges like 500-599 or 5xx to only report issues for",
+        )
+        .long(OPT_ONLY_STATUS)
+        .value_name("status codes")
+        .takes_value(true)
+        .required(false);
+
+    let opt_pre_resolve_hosts = Arg::new(OPT_PRE_RESOLVE_HOSTS)
+        .help("Resolve every host once before checking URLs and fail URLs on unresolvable hosts")
+        .long(OPT_PRE_RESOLVE_HOSTS)
+        .takes_value(false)
+        .required(false);
+
+    let opt_show_all = Arg::new(OPT_SHOW_ALL)
+        .help("Also write the URLs that are OK or allowed to not be OK")
+        .long(OPT_SHOW_ALL)
+        .takes_value(false)
+        .required(false);
+
+    let opt_spread_over = Arg::new(OPT_SPREAD_OVER)
+        .help("Space out requests so checking all URLs takes about this many seconds")
+        .long(OPT_SPREAD_OVER)
+        .value_name("seconds")
+        .takes_value(true)
+        .required(false);
+
+    let opt_step_summary = Arg::new(OPT_STEP_SUMMARY)
+        .help("Append a Markdown summary to this file (default: $GITHUB_STEP_SUMMARY)")
+        .long(OPT_STEP_SUMMARY)
+        .value_name("path")
+        .takes_value(true)
+        .required(false);
+
+    let opt_no_redirects_ok = Arg::new(OPT_NO_REDIRECTS_OK)
+        .help("Redirects that are not followed, e.g. without a location, are issues")
+        .long(OPT_NO_REDIRECTS_OK)
+        .takes_value(false)
+        .required(false);
+
+    let opt_preflight = Arg::new(OPT_PREFLIGHT)
+        .help("Check that every host can be reached before checking URLs")
+        .long(OPT_PREFLIGHT)
+        .takes_value(false)
+        .required(false);
+
+    let opt_skip_unreachable_hosts = Arg::new(OPT_SKIP_UNREACHABLE_HOSTS)
+        .help("Do not check URLs on hosts that can not be reached, implies --preflight")
+        .long(OPT_SKIP_UNREACHABLE_HOSTS)
+        .takes_value(false)
+        .required(false);
+
+    let opt_header_rule = Arg::new(OPT_HEADER_RULE)
+        .help("Header to send to URLs matching the regex, like '^https://api\\. Accept: application/json', can be used multiple times")
+        .long(OPT_HEADER_RULE)
+        .value_name("regex header")
+        .takes_value(true)
+        .multiple_occurrences(true)
+        .required(false);
+
+    let opt_no_follow_redirects = Arg::new(OPT_NO_FOLLOW_REDIRECTS)
+        .help("Use the status of the first response instead of following redirects")
+        .long(OPT_NO_FOLLOW_REDIRECTS)
+        .takes_value(false)
+        .required(false);
+
+    let opt_sort_by = Arg::new(OPT_SORT_BY)
+        .help("Order of the results (default: file)")
+        .long(OPT_SORT_BY)
+        .value_name("key")
+        .possible_values(["url", "status", "file", "time"])
+        .takes_value(true)
+        .required(false);
+
+    let opt_min_tls_version = Arg::new(OPT_MIN_TLS_VERSION)
+        .help("Lowest TLS version to accept, 1.0, 1.1, 1.2 or 1.3, URLs only supporting older versions are issues")
+        .long(OPT_MIN_TLS_VERSION)
+        .value_name("version")
+        .validator(validator::tls_version)
+        .takes_value(true)
+        .required(false);
+
+    let opt_unique_per_file = Arg::new(OPT_UNIQUE_PER_FILE)
+        .help("Report broken URLs once for every file they are in, with their occurrence count")
+        .long(OPT_UNIQUE_PER_FILE)
+        .takes_value(false)
+        .required(false);
+
+    let opt_url_rewrite = Arg::new(OPT_URL_REWRITE)
+        .help("Regex and replacement to rewrite URLs with before checking them, like 'https://prod\\.=>https://staging.', can be used multiple times")
+        .long(OPT_URL_REWRITE)
+        .value_name("regex=>replacement")
+        .takes_value(true)
+        .multiple_occurrences(true)
+        .required(false);
+
+    let opt_stats = Arg::new(OPT_STATS)
+        .help("Print the number of checked URLs by status code")
+        .long(OPT_STATS)
+        .takes_value(false)
+        .required(false);
+
+    let opt_fail_if_empty = Arg::new(OPT_FAIL_IF_EMPTY)
+        .help("Exit with status 1 when no URLs are checked, e.g. when none are found")
+        .long(OPT_FAIL_IF_EMPTY)
+        .takes_value(false)
+        .required(false);
+
+    let opt_host_overrides = Arg::new(OPT_HOST_OVERRIDES)
+        .help("Comma separated host=ip or host=ip:port addresses to connect to instead of resolving the hosts")
+        .long(OPT_HOST_OVERRIDES)
+        .value_name("host=ip[:port]")
+        .validator(parse_host_overrides)
+        .takes_value(true)
+        .required(false);
+
+    let opt_suggest_fixes = Arg::new(OPT_SUGGEST_FIXES)
+        .help("Look for OK variants of broken URLs, like with https or a trailing slash")
+        .long(OPT_SUGGEST_FIXES)
+        .takes_value(false)
+        .required(false);
+
+    let opt_user_agent = Arg::new(OPT_USER_AGENT)
+        .help("User agent to send instead of the urlsup one, when used multiple times they take turns")
+        .long(OPT_USER_AGENT)
+        .value_name("user agent")
+        .takes_value(true)
+        .multiple_occurrences(true)
+        .required(false);
+
+    let opt_frontmatter_only = Arg::new(OPT_FRONTMATTER_ONLY)
+        .help("Only check URLs in the frontmatter of Markdown files")
+        .long(OPT_FRONTMATTER_ONLY)
+        .takes_value(false)
+        .required(false);
+
+    let opt_report_unreachable_only = Arg::new(OPT_REPORT_UNREACHABLE_ONLY)
+        .help("Only report URLs on hosts that can not be reached, e.g. DNS, connection and timeout errors")
+        .long(OPT_REPORT_UNREACHABLE_ONLY)
+        .takes_value(false)
+        .required(false);
+
+    let opt_respect_robots = Arg::new(OPT_RESPECT_ROBOTS)
+        .help("Wait between requests to hosts as long as their robots.txt Crawl-delay asks for")
+        .long(OPT_RESPECT_ROBOTS)
+        .takes_value(false)
+        .required(false);
+
+    let opt_urls_from = Arg::new(OPT_URLS_FROM)
+        .help("Also check the URLs in this file, one URL per line")
+        .long(OPT_URLS_FROM)
+        .value_name("file")
+        .validator_os(exists_on_filesystem)
+        .takes_value(true)
+        .required(false);
+
+    let opt_group_by_url = Arg::new(OPT_GROUP_BY_URL)
+        .help("List every broken URL once with all the files it is in, implies --unique-per-file")
+        .long(OPT_GROUP_BY_URL)
+        .takes_value(false)
+        .required(false);
+
+    let opt_show_context = Arg::new(OPT_SHOW_CONTEXT)
+        .help("Show the line broken URLs are on beneath them in the text format")
+        .long(OPT_SHOW_CONTEXT)
+        .takes_value(false)
+        .required(false);
+
+    let opt_check_duplicates = Arg::new(OPT_CHECK_DUPLICATES)
+        .help("List URLs that are in the same file more than once, with their lines")
+        .long(OPT_CHECK_DUPLICATES)
+        .takes_value(false)
+        .required(false);
+
+    let opt_pool_max_idle_per_host = Arg::new(OPT_POOL_MAX_IDLE_PER_HOST)
+        .help("Max number of idle connections to keep open to every host")
+        .long(OPT_POOL_MAX_IDLE_PER_HOST)
+        .value_name("count")
+        .takes_value(true)
+        .required(false);
+
+    let opt_pool_idle_timeout = Arg::new(OPT_POOL_IDLE_TIMEOUT)
+        .help("Seconds to keep idle connections open (default: 90)")
+        .long(OPT_POOL_IDLE_TIMEOUT)
+        .value_name("seconds")
+        .takes_value(true)
+        .required(false);
+
+    let opt_tcp_keepalive = Arg::new(OPT_TCP_KEEPALIVE)
+        .help("Seconds between TCP keepalive probes (default: none are sent)")
+        .long(OPT_TCP_KEEPALIVE)
+        .value_name("seconds")
+        .takes_value(true)
+        .required(false);
+
+    let opt_report_skipped_schemes = Arg::new(OPT_REPORT_SKIPPED_SCHEMES)
+        .help("Report data: and javascript: URLs as issues instead of skipping them")
+        .long(OPT_REPORT_SKIPPED_SCHEMES)
+        .takes_value(false)
+        .required(false);
+
+    let opt_resume = Arg::new(OPT_RESUME)
+        .help("Save results to this file while checking, and skip the URLs in it when checking the same URLs again")
+        .long(OPT_RESUME)
+        .value_name("file")
+        .takes_value(true)
+        .required(false);
+
+    let opt_warn_on_embedded_credentials = Arg::new(OPT_WARN_ON_EMBEDDED_CREDENTIALS)
+        .help("List URLs with a user name or password in them, which are masked in the results")
+        .long(OPT_WARN_ON_EMBEDDED_CREDENTIALS)
+        .takes_value(false)
+        .required(false);
+
+    let opt_report_redirects = Arg::new(OPT_REPORT_REDIRECTS)
+        .help("List OK URLs that were redirected, with where they ended, apart from the issues")
+        .long(OPT_REPORT_REDIRECTS)
+        .takes_value(false)
+        .required(false);
+
+    let opt_delay_between_files = Arg::new(OPT_DELAY_BETWEEN_FILES)
+        .help("Check URLs file by file, pausing this many milliseconds before the URLs of every next file")
+        .long(OPT_DELAY_BETWEEN_FILES)
+        .value_name("ms")
+        .takes_value(true)
+        .required(false);
+
+    let opt_check_both_schemes = Arg::new(OPT_CHECK_BOTH_SCHEMES)
+        .help("Also check URLs with the other of http and https, reporting the ones only OK with one of them")
+        .long(OPT_CHECK_BOTH_SCHEMES)
+        .takes_value(false)
+        .required(false);
+
+    let opt_max_broken = Arg::new(OPT_MAX_BROKEN)
+        .help("Exit with status 0 when there are at most this many issues (default: 0)")
+        .long(OPT_MAX_BROKEN)
+        .value_name("count")
+        .takes_value(true)
+        .required(false);
+
+    let opt_comments_only = Arg::new(OPT_COMMENTS_ONLY)
+        .help("Only check URLs in comments in Rust, Python, JavaScript and Go files")
+        .long(OPT_COMMENTS_ONLY)
+        .takes_value(false)
+        .required(false);
+
+    let opt_since_last_run = Arg::new(OPT_SINCE_LAST_RUN)
+        .help(
+            "Save results to this file, and only check the URLs that were not OK in it or are new",
+        )
+        .long(OPT_SINCE_LAST_RUN)
+        .value_name("file")
+        .takes_value(true)
+        .required(false);
+
+    let cmd_check = Command::new(CMD_CHECK)
+        .about("Check a single URL and show every response on the way to the result")
+        .arg(
+            Arg::new(OPT_URL)
+                .help("URL to check")
+                .required(true)
+                .index(1),
+        );
+
     let matches = Command::new("urls_up")
         .version(crate_version!())
         .author(crate_authors!())
@@ -84,7 +544,64 @@ async fn main() {
         .arg(opt_timeout)
         .arg(opt_allow)
         .arg(opt_threads)
+        .arg(opt_concurrency_per_host)
         .arg(opt_allow_timeout)
+        .arg(opt_connect_timeout)
+        .arg(opt_webhook_url)
+        .arg(opt_webhook_max_urls)
+        .arg(opt_no_inline_ignores)
+        .arg(opt_host_timeouts)
+        .arg(opt_max_file_size)
+        .arg(opt_white_list_pattern)
+        .arg(opt_max_failures)
+        .arg(opt_max_requests)
+        .arg(opt_output)
+        .arg(opt_format)
+        .arg(opt_max_runtime)
+        .arg(opt_allow_timeout_hosts)
+        .arg(opt_cookies)
+        .arg(opt_fail_on_offsite_redirect)
+        .arg(opt_allowed_redirect_hosts)
+        .arg(opt_only_status)
+        .arg(opt_pre_resolve_hosts)
+        .arg(opt_show_all)
+        .arg(opt_spread_over)
+        .arg(opt_step_summary)
+        .arg(opt_no_redirects_ok)
+        .arg(opt_preflight)
+        .arg(opt_skip_unreachable_hosts)
+        .arg(opt_header_rule)
+        .arg(opt_no_follow_redirects)
+        .arg(opt_sort_by)
+        .arg(opt_min_tls_version)
+        .arg(opt_unique_per_file)
+        .arg(opt_url_rewrite)
+        .arg(opt_stats)
+        .arg(opt_fail_if_empty)
+        .arg(opt_host_overrides)
+        .arg(opt_suggest_fixes)
+        .arg(opt_user_agent)
+        .arg(opt_frontmatter_only)
+        .arg(opt_report_unreachable_only)
+        .arg(opt_respect_robots)
+        .arg(opt_urls_from)
+        .arg(opt_group_by_url)
+        .arg(opt_show_context)
+        .arg(opt_check_duplicates)
+        .arg(opt_pool_max_idle_per_host)
+        .arg(opt_pool_idle_timeout)
+        .arg(opt_tcp_keepalive)
+        .arg(opt_report_skipped_schemes)
+        .arg(opt_resume)
+        .arg(opt_warn_on_embedded_credentials)
+        .arg(opt_report_redirects)
+        .arg(opt_delay_between_files)
+        .arg(opt_check_both_schemes)
+        .arg(opt_max_broken)
+        .arg(opt_comments_only)
+        .arg(opt_since_last_run)
+        .subcommand(cmd_check)
+        .subcommand_negates_reqs(true)
         .get_matches();
 
     let urls_up = UrlsUp::new(Finder::default(), Validator::default());
@@ -94,6 +611,56 @@ async fn main() {
         allowed_status_codes: None,
         thread_count: num_cpus::get(),
         allow_timeout: matches.is_present(OPT_ALLOW_TIMEOUT),
+        connect_timeout: None,
+        webhook_url: matches.value_of(OPT_WEBHOOK_URL).map(String::from),
+        webhook_max_urls: DEFAULT_WEBHOOK_MAX_URLS,
+        inline_ignores: !matches.is_present(OPT_NO_INLINE_IGNORES),
+        host_timeouts: None,
+        max_file_size: Some(DEFAULT_MAX_FILE_SIZE),
+        white_list_patterns: None,
+        max_failures: None,
+        max_runtime: None,
+        allow_timeout_hosts: None,
+        cookies: matches.is_present(OPT_COOKIES),
+        interrupt: None,
+        fail_on_offsite_redirect: matches.is_present(OPT_FAIL_ON_OFFSITE_REDIRECT),
+        allowed_redirect_hosts: None,
+        concurrency_per_host: None,
+        pre_resolve_hosts: matches.is_present(OPT_PRE_RESOLVE_HOSTS),
+        include_success: matches.is_present(OPT_SHOW_ALL),
+        spread_over: None,
+        redirects_ok: !matches.is_present(OPT_NO_REDIRECTS_OK),
+        preflight: matches.is_present(OPT_PREFLIGHT),
+        skip_unreachable_hosts: matches.is_present(OPT_SKIP_UNREACHABLE_HOSTS),
+        header_rules: None,
+        follow_redirects: !matches.is_present(OPT_NO_FOLLOW_REDIRECTS),
+        min_tls_version: matches.value_of(OPT_MIN_TLS_VERSION).map(String::from),
+        unique_per_file: matches.is_present(OPT_UNIQUE_PER_FILE)
+            || matches.is_present(OPT_GROUP_BY_URL),
+        url_rewrites: None,
+        host_overrides: None,
+        suggest_fixes: matches.is_present(OPT_SUGGEST_FIXES),
+        user_agents: matches
+            .values_of(OPT_USER_AGENT)
+            .map(|user_agents| user_agents.map(String::from).collect()),
+        frontmatter_only: matches.is_present(OPT_FRONTMATTER_ONLY),
+        report_unreachable_only: matches.is_present(OPT_REPORT_UNREACHABLE_ONLY),
+        respect_robots: matches.is_present(OPT_RESPECT_ROBOTS),
+        urls_from: matches.value_of(OPT_URLS_FROM).map(PathBuf::from),
+        show_context: matches.is_present(OPT_SHOW_CONTEXT),
+        max_requests: None,
+        check_duplicates: matches.is_present(OPT_CHECK_DUPLICATES),
+        pool_max_idle_per_host: None,
+        pool_idle_timeout: None,
+        tcp_keepalive: None,
+        report_skipped_schemes: matches.is_present(OPT_REPORT_SKIPPED_SCHEMES),
+        resume_file: matches.value_of(OPT_RESUME).map(PathBuf::from),
+        warn_on_embedded_credentials: matches.is_present(OPT_WARN_ON_EMBEDDED_CREDENTIALS),
+        report_redirects: matches.is_present(OPT_REPORT_REDIRECTS),
+        delay_between_files: None,
+        check_both_schemes: matches.is_present(OPT_CHECK_BOTH_SCHEMES),
+        comments_only: matches.is_present(OPT_COMMENTS_ONLY),
+        last_run_file: matches.value_of(OPT_SINCE_LAST_RUN).map(PathBuf::from),
     };
 
     if let Some(white_list_urls) = matches.value_of(OPT_WHITE_LIST) {
@@ -107,6 +674,67 @@ async fn main() {
         opts.white_list = Some(white_list);
     }
 
+    if let Some(hosts) = matches.value_of(OPT_ALLOW_TIMEOUT_HOSTS) {
+        let allow_timeout_hosts: Vec<String> = hosts
+            .split(',')
+            .filter_map(|s| match s.is_empty() {
+                true => None,
+                false => Some(s.to_string()),
+            })
+            .collect();
+        opts.allow_timeout_hosts = Some(allow_timeout_hosts);
+    }
+
+    if let Some(hosts) = matches.value_of(OPT_ALLOWED_REDIRECT_HOSTS) {
+        let allowed_redirect_hosts: Vec<String> = hosts
+            .split(',')
+            .filter_map(|s| match s.is_empty() {
+                true => None,
+                false => Some(s.to_string()),
+            })
+            .collect();
+        opts.allowed_redirect_hosts = Some(allowed_redirect_hosts);
+    }
+
+    if let Some(patterns) = matches.values_of(OPT_WHITE_LIST_PATTERN) {
+        let white_list_patterns: Vec<Regex> = patterns
+            .map(|pattern| {
+                Regex::new(pattern)
+                    .unwrap_or_else(|e| panic!("Could not parse {} into a regex: {}", pattern, e))
+            })
+            .collect();
+        opts.white_list_patterns = Some(white_list_patterns);
+    }
+
+    // URLs matching the patterns in the ignore file are never reported
+    if let Ok(content) = fs::read_to_string(IGNORE_FILE) {
+        let patterns = ignore_file::parse(&content)
+            .unwrap_or_else(|e| panic!("Could not parse {}: {}", IGNORE_FILE, e));
+        opts.white_list_patterns
+            .get_or_insert_with(Vec::new)
+            .extend(patterns);
+    }
+
+    if let Some(header_rules) = matches.values_of(OPT_HEADER_RULE) {
+        let header_rules: Vec<HeaderRule> = header_rules.map(parse_header_rule).collect();
+        opts.header_rules = Some(header_rules);
+    }
+
+    if let Some(url_rewrites) = matches.values_of(OPT_URL_REWRITE) {
+        let url_rewrites: Vec<(Regex, String)> = url_rewrites
+            .map(|url_rewrite| match url_rewrite.split_once("=>") {
+                Some((pattern, replacement)) => {
+                    let pattern = Regex::new(pattern).unwrap_or_else(|e| {
+                        panic!("Could not parse {} into a regex: {}", pattern, e)
+                    });
+                    (pattern, replacement.to_string())
+                }
+                None => panic!("Could not parse {} into regex=>replacement", url_rewrite),
+            })
+            .collect();
+        opts.url_rewrites = Some(url_rewrites);
+    }
+
     if let Some(str_timeout) = matches.value_of(OPT_TIMEOUT) {
         let timeout: Duration = str_timeout
             .parse()
@@ -115,18 +743,77 @@ async fn main() {
         opts.timeout = timeout;
     }
 
-    if let Some(allowed_status_codes) = matches.value_of(OPT_ALLOW) {
-        let allowed: Vec<u16> = allowed_status_codes
+    if let Some(str_connect_timeout) = matches.value_of(OPT_CONNECT_TIMEOUT) {
+        let connect_timeout: Duration = str_connect_timeout
+            .parse()
+            .map(Duration::from_secs)
+            .unwrap_or_else(|_| {
+                panic!("Could not parse {} into an int (u64)", str_connect_timeout)
+            });
+        if connect_timeout > opts.timeout {
+            panic!(
+                "Connect timeout ({}) can't be larger than timeout ({})",
+                connect_timeout.as_secs(),
+                opts.timeout.as_secs()
+            );
+        }
+        opts.connect_timeout = Some(connect_timeout);
+    }
+
+    if let Some(pool_max_idle_per_host) = matches.value_of(OPT_POOL_MAX_IDLE_PER_HOST) {
+        let pool_max_idle_per_host = pool_max_idle_per_host.parse::<usize>().unwrap_or_else(|_| {
+            panic!(
+                "Could not parse {} into an int (usize)",
+                pool_max_idle_per_host
+            )
+        });
+        opts.pool_max_idle_per_host = Some(pool_max_idle_per_host);
+    }
+
+    if let Some(pool_idle_timeout) = matches.value_of(OPT_POOL_IDLE_TIMEOUT) {
+        let pool_idle_timeout = pool_idle_timeout
+            .parse()
+            .map(Duration::from_secs)
+            .unwrap_or_else(|_| panic!("Could not parse {} into an int (u64)", pool_idle_timeout));
+        opts.pool_idle_timeout = Some(pool_idle_timeout);
+    }
+
+    if let Some(tcp_keepalive) = matches.value_of(OPT_TCP_KEEPALIVE) {
+        let tcp_keepalive = tcp_keepalive
+            .parse()
+            .map(Duration::from_secs)
+            .unwrap_or_else(|_| panic!("Could not parse {} into an int (u64)", tcp_keepalive));
+        opts.tcp_keepalive = Some(tcp_keepalive);
+    }
+
+    if let Some(host_timeouts) = matches.value_of(OPT_HOST_TIMEOUTS) {
+        let host_timeouts: HashMap<String, Duration> = host_timeouts
             .split(',')
-            .filter_map(|s| match s.is_empty() {
-                true => None,
-                false => Some(
-                    s.parse::<u16>()
-                        .expect("Could not parse status code to int (u16)"),
-                ),
+            .filter(|s| !s.is_empty())
+            .map(|s| match s.split_once('=') {
+                Some((host, seconds)) => {
+                    let timeout = seconds
+                        .parse()
+                        .map(Duration::from_secs)
+                        .unwrap_or_else(|_| {
+                            panic!("Could not parse {} into an int (u64)", seconds)
+                        });
+                    (host.to_string(), timeout)
+                }
+                None => panic!("Could not parse {} into host=seconds", s),
             })
             .collect();
-        opts.allowed_status_codes = Some(allowed);
+        opts.host_timeouts = Some(host_timeouts);
+    }
+
+    if let Some(host_overrides) = matches.value_of(OPT_HOST_OVERRIDES) {
+        // Already validated when parsing the arguments
+        let host_overrides = parse_host_overrides(host_overrides).unwrap();
+        opts.host_overrides = Some(host_overrides);
+    }
+
+    if let Some(allowed_status_codes) = matches.value_of(OPT_ALLOW) {
+        opts.allowed_status_codes = Some(parse_status_codes(allowed_status_codes));
     }
 
     if let Some(thread_count) = matches.value_of(OPT_THREADS) {
@@ -135,27 +822,355 @@ async fn main() {
             .unwrap_or_else(|_| panic!("Could not parse {} into an int (usize)", thread_count));
     }
 
-    if let Some(files) = matches.values_of(OPT_FILES) {
-        let paths = files.map(Path::new).collect::<Vec<&Path>>();
+    if let Some(concurrency_per_host) = matches.value_of(OPT_CONCURRENCY_PER_HOST) {
+        let concurrency_per_host = concurrency_per_host.parse::<usize>().unwrap_or_else(|_| {
+            panic!(
+                "Could not parse {} into an int (usize)",
+                concurrency_per_host
+            )
+        });
+        if opts.thread_count > 0 && concurrency_per_host > opts.thread_count {
+            panic!(
+                "Concurrency per host ({}) can not be higher than the thread count ({})",
+                concurrency_per_host, opts.thread_count
+            );
+        }
+        opts.concurrency_per_host = Some(concurrency_per_host);
+    }
+
+    if let Some(max_file_size) = matches.value_of(OPT_MAX_FILE_SIZE) {
+        let max_file_size = max_file_size
+            .parse::<u64>()
+            .unwrap_or_else(|_| panic!("Could not parse {} into an int (u64)", max_file_size));
+        opts.max_file_size = Some(max_file_size);
+    }
+
+    if let Some(max_failures) = matches.value_of(OPT_MAX_FAILURES) {
+        let max_failures = max_failures
+            .parse::<usize>()
+            .unwrap_or_else(|_| panic!("Could not parse {} into an int (usize)", max_failures));
+        opts.max_failures = Some(max_failures);
+    }
+
+    if let Some(max_requests) = matches.value_of(OPT_MAX_REQUESTS) {
+        let max_requests = max_requests
+            .parse::<usize>()
+            .unwrap_or_else(|_| panic!("Could not parse {} into an int (usize)", max_requests));
+        opts.max_requests = Some(max_requests);
+    }
+
+    if let Some(max_runtime) = matches.value_of(OPT_MAX_RUNTIME) {
+        let max_runtime = max_runtime
+            .parse()
+            .map(Duration::from_secs)
+            .unwrap_or_else(|_| panic!("Could not parse {} into an int (u64)", max_runtime));
+        opts.max_runtime = Some(max_runtime);
+    }
+
+    if let Some(spread_over) = matches.value_of(OPT_SPREAD_OVER) {
+        let spread_over = spread_over
+            .parse()
+            .map(Duration::from_secs)
+            .unwrap_or_else(|_| panic!("Could not parse {} into an int (u64)", spread_over));
+        opts.spread_over = Some(spread_over);
+    }
+
+    if let Some(delay_between_files) = matches.value_of(OPT_DELAY_BETWEEN_FILES) {
+        let delay_between_files = delay_between_files
+            .parse()
+            .map(Duration::from_millis)
+            .unwrap_or_else(|_| {
+                panic!("Could not parse {} into an int (u64)", delay_between_files)
+            });
+        opts.delay_between_files = Some(delay_between_files);
+    }
+
+    if let Some(max_urls) = matches.value_of(OPT_WEBHOOK_MAX_URLS) {
+        opts.webhook_max_urls = max_urls
+            .parse::<usize>()
+            .unwrap_or_else(|_| panic!("Could not parse {} into an int (usize)", max_urls));
+    }
 
-        match urls_up.run(paths, opts).await {
-            Ok(result) => {
-                if result.is_empty() {
-                    println!("\n\n> No issues!");
+    let (interrupt_sender, interrupt) = watch::channel(false);
+    tokio::spawn(async move {
+        // Report the URLs checked so far on the first Ctrl-C and exit right away on the second
+        if tokio::signal::ctrl_c().await.is_ok() {
+            let _ = interrupt_sender.send(true);
+        }
+        if tokio::signal::ctrl_c().await.is_ok() {
+            std::process::exit(130)
+        }
+    });
+    opts.interrupt = Some(interrupt);
+
+    if let Some(check_matches) = matches.subcommand_matches(CMD_CHECK) {
+        let url = check_matches.value_of(OPT_URL).unwrap();
+        match check(url, &opts).await {
+            true => std::process::exit(1),
+            false => std::process::exit(0),
+        }
+    }
+
+    let paths = matches
+        .values_of(OPT_FILES)
+        .map(|files| files.map(Path::new).collect::<Vec<&Path>>())
+        .unwrap_or_default();
+
+    let max_broken = matches
+        .value_of(OPT_MAX_BROKEN)
+        .map(|max_broken| {
+            max_broken
+                .parse::<usize>()
+                .unwrap_or_else(|_| panic!("Could not parse {} into an int (usize)", max_broken))
+        })
+        .unwrap_or(0);
+
+    let mut result_writer = ResultWriter {
+        output: matches.value_of(OPT_OUTPUT).map(String::from),
+        format: matches.value_of(OPT_FORMAT).unwrap_or("text").to_string(),
+        sort_by: matches.value_of(OPT_SORT_BY).unwrap_or("file").to_string(),
+        stats: matches.is_present(OPT_STATS),
+        group_by_url: matches.is_present(OPT_GROUP_BY_URL),
+        only_status_codes: matches.value_of(OPT_ONLY_STATUS).map(parse_status_codes),
+        step_summary: matches
+            .value_of(OPT_STEP_SUMMARY)
+            .map(String::from)
+            .or_else(|| env::var(GITHUB_STEP_SUMMARY).ok()),
+    };
+
+    match urls_up.run_with_sink(paths, opts, &mut result_writer).await {
+        Ok(report) => {
+            if report.interrupted {
+                std::process::exit(130)
+            } else if report.max_runtime_exceeded {
+                std::process::exit(2)
+            } else if report.max_requests_reached {
+                std::process::exit(3)
+            } else if report.issues.len() > max_broken {
+                std::process::exit(1)
+            } else if !report.issues.is_empty() {
+                eprintln!(
+                    "\n> {} issue(s), at most {} allowed",
+                    report.issues.len(),
+                    max_broken
+                );
+            } else if report.checked_count == 0 && matches.is_present(OPT_FAIL_IF_EMPTY) {
+                eprintln!("\n> No URLs were checked");
+                std::process::exit(1)
+            }
+        }
+        Err(e) => panic!("{}", e),
+    }
+}
+
+// Checks a single URL and writes every response on the way to stdout, true if it is an issue
+async fn check(url: &str, opts: &UrlsUpOptions) -> bool {
+    eprintln!("> Checking {}\n", validator::mask_credentials(url));
+
+    let url_location = UrlLocation {
+        url: url.to_string(),
+        line: 0,
+        file_name: String::new(),
+        allowed_statuses: vec![],
+        context: None,
+    };
+    // The result is of the traced responses, a second request could get other responses
+    let (hops, vr) = validator::trace(url_location, opts).await;
+
+    print!("{}", format::trace(&hops, &vr, &vr.allowed_by(opts)));
+    vr.is_issue(opts)
+}
+
+// Writes the results to stdout or the output file once all URLs are checked
+struct ResultWriter {
+    output: Option<String>,
+    format: String,
+    sort_by: String,
+    stats: bool,
+    // List every broken URL once in the text format
+    group_by_url: bool,
+    // Only these are written, the exit code still depends on all issues
+    only_status_codes: Option<Vec<u16>>,
+    // File to append a Markdown summary to, in addition to the results
+    step_summary: Option<String>,
+}
+
+impl ResultSink for ResultWriter {
+    fn on_finish(&mut self, report: &Report) {
+        let mut issues: Vec<ValidationResult> = report
+            .issues
+            .iter()
+            .filter(|vr| match &self.only_status_codes {
+                Some(only_status_codes) => vr
+                    .status_code
+                    .is_some_and(|status_code| only_status_codes.contains(&status_code)),
+                None => true,
+            })
+            .cloned()
+            .collect();
+        let mut successes = report.successes.clone();
+        let mut redirects = report.redirects.clone();
+        sort_results(&mut issues, &self.sort_by);
+        sort_results(&mut successes, &self.sort_by);
+        sort_results(&mut redirects, &self.sort_by);
+        let report = &Report {
+            checked_count: report.checked_count,
+            issues,
+            max_runtime_exceeded: report.max_runtime_exceeded,
+            interrupted: report.interrupted,
+            successes,
+            redirects,
+            status_counts: report.status_counts.clone(),
+            max_requests_reached: report.max_requests_reached,
+        };
+
+        let written = match &self.output {
+            Some(output) => File::create(output)
+                .and_then(|mut file| {
+                    write_results(&mut file, report, &self.format, self.group_by_url)
+                })
+                .map(|_| eprintln!("\n\n> Wrote results to {}", output)),
+            None => {
+                eprint!("\n\n");
+                write_results(&mut io::stdout(), report, &self.format, self.group_by_url)
+            }
+        };
+        written.unwrap_or_else(|e| panic!("Could not write results: {}", e));
+
+        if self.stats {
+            eprint!("\n{}", format::stats(report));
+        }
+
+        if let Some(step_summary) = &self.step_summary {
+            let appended = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(step_summary)
+                .and_then(|mut file| write!(file, "{}", format::markdown(report)));
+            if let Err(e) = appended {
+                eprintln!("> Warning: could not write step summary: {}", e);
+            }
+        }
+    }
+}
+
+fn write_results(
+    out: &mut impl Write,
+    report: &Report,
+    format: &str,
+    group_by_url: bool,
+) -> io::Result<()> {
+    match format {
+        "markdown" => write!(out, "{}", format::markdown(report)),
+        "ndjson" => write!(out, "{}", format::ndjson(report)),
+        _ => {
+            if report.issues.is_empty() {
+                writeln!(out, "> No issues!")?;
+            } else {
+                writeln!(out, "> Issues")?;
+                if group_by_url {
+                    write!(out, "{}", format::grouped_by_url(&report.issues))?;
                 } else {
-                    println!("\n\n> Issues");
-                    for (i, validation_result) in result.iter().enumerate() {
-                        println!("{:4}. {}", i + 1, validation_result);
+                    for (i, validation_result) in report.issues.iter().enumerate() {
+                        writeln!(out, "{:4}. {}", i + 1, validation_result)?;
+                        if let Some(context) = &validation_result.context {
+                            writeln!(out, "      {}", context)?;
+                        }
                     }
+                }
+            }
+
+            if !report.redirects.is_empty() {
+                write!(out, "{}", format::redirects(&report.redirects))?;
+            }
 
-                    std::process::exit(1)
+            if !report.successes.is_empty() {
+                writeln!(out, "> OK")?;
+                for (i, validation_result) in report.successes.iter().enumerate() {
+                    writeln!(out, "{:4}. {}", i + 1, validation_result)?;
                 }
             }
-            Err(e) => panic!("{}", e),
+
+            Ok(())
         }
     }
 }
 
+// Expands comma separated status codes, ranges and classes, e.g. 404,500-599,4xx
+fn parse_status_codes(status_codes: &str) -> Vec<u16> {
+    status_codes
+        .split(',')
+        .filter(|s| !s.is_empty())
+        .flat_map(|s| {
+            let parse = |status_code: &str| {
+                status_code
+                    .trim()
+                    .parse::<u16>()
+                    .unwrap_or_else(|_| panic!("Could not parse {} into status codes", s))
+            };
+            let s_lowercase = s.trim().to_lowercase();
+            let (from, to) = match s_lowercase.strip_suffix("xx") {
+                Some(class) => match parse(class) {
+                    class @ 1..=5 => (class * 100, class * 100 + 99),
+                    _ => panic!("Status code classes must be between 1xx and 5xx, got {}", s),
+                },
+                None => {
+                    let (from, to) = s.split_once('-').unwrap_or((s, s));
+                    (parse(from), parse(to))
+                }
+            };
+            if from < 100 || to > 599 || from > to {
+                panic!("Status codes must be between 100 and 599, got {}", s)
+            }
+            from..=to
+        })
+        .collect()
+}
+
+// Splits a rule like '^https://api\. Accept: application/json' into its regex and header
+fn parse_header_rule(header_rule: &str) -> HeaderRule {
+    let parsed =
+        header_rule
+            .trim()
+            .split_once(char::is_whitespace)
+            .and_then(|(pattern, header)| {
+                let (name, value) = header.split_once(':')?;
+                let name = HeaderName::from_bytes(name.trim().as_bytes()).ok()?;
+                let value = HeaderValue::from_str(value.trim()).ok()?;
+                Some((pattern, name, value))
+            });
+    let (pattern, name, value) = parsed.unwrap_or_else(|| {
+        panic!(
+            "Could not parse {} into a regex and a header like 'name: value'",
+            header_rule
+        )
+    });
+    let pattern = Regex::new(pattern)
+        .unwrap_or_else(|e| panic!("Could not parse {} into a regex: {}", pattern, e));
+
+    let mut headers = HeaderMap::new();
+    headers.insert(name, value);
+    HeaderRule { pattern, headers }
+}
+
+// Splits comma separated host=ip[:port] overrides, a port of 0 keeps the port in the URL
+fn parse_host_overrides(host_overrides: &str) -> Result<HashMap<String, SocketAddr>, String> {
+    host_overrides
+        .split(',')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let (host, addr) = s
+                .split_once('=')
+                .ok_or_else(|| format!("Could not parse {} into host=ip[:port]", s))?;
+            let addr = addr
+                .parse::<SocketAddr>()
+                .or_else(|_| addr.parse::<IpAddr>().map(|ip| SocketAddr::new(ip, 0)))
+                .map_err(|_| format!("Could not parse {} into an IP address", addr))?;
+            Ok((host.to_string(), addr))
+        })
+        .collect()
+}
+
 fn exists_on_filesystem(path: &OsStr) -> Result<(), String> {
     match Some(path).map(Path::new).map(Path::exists).unwrap_or(false) {
         true => Ok(()),
@@ -1,37 +1,70 @@
 use grep::regex::RegexMatcher;
-use grep::searcher::sinks::UTF8;
+use grep::searcher::sinks::Lossy;
 use grep::searcher::Searcher;
 use linkify::{LinkFinder, LinkKind};
+use once_cell::sync::Lazy;
+use regex::Regex;
 
-use crate::UrlLocation;
+use crate::{UrlLocation, UrlsUpOptions};
 
+use std::fs;
 use std::io;
 use std::path::Path;
 
 const MARKDOWN_URL_PATTERN: &str =
     r#"(http://|https://)[a-z0-9]+([-.]{1}[a-z0-9]+)*(.[a-z]{2,5})?(:[0-9]{1,5})?(/.*)?"#;
 
+static HTML_URL_ATTRIBUTE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)\b(href|src|action|srcset)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+))"#).unwrap()
+});
+
+// Comments, and scripts and styles with their contents in between their tags
+static HTML_NON_MARKUP_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?is)<!--.*?-->|(?P<open><(?:script|style)\b[^>]*>)(?P<inner>.*?)(?P<close></(?:script|style)\s*>)",
+    )
+    .unwrap()
+});
+
+// Character references like &amp;, &#38; and &#x26;
+static HTML_CHARACTER_REFERENCE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"&(?:#(\d+)|#[xX]([0-9a-fA-F]+)|(amp|lt|gt|quot|apos));").unwrap());
+
+// URLs in reStructuredText and AsciiDoc end where the markup around them starts, e.g.
+// `text <https://example.com>`_ or link:https://example.com[text]
+static LIGHTWEIGHT_MARKUP_URL_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"https?://[^\s<>\[\]`|]+").unwrap());
+
+static JSON_UNICODE_ESCAPE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\\u([0-9a-fA-F]{4})").unwrap());
+
+// Allows the given status codes for URLs on the same line, e.g. <!-- urlsup-allow 403,429 -->
+static ALLOW_DIRECTIVE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<!--\s*urlsup-allow\s+([0-9,\s]+?)\s*-->").unwrap());
+
+// Ignores URLs on the same line
+const IGNORE_DIRECTIVE: &str = "<!-- urlsup-ignore -->";
+// Ignores URLs on the following line
+const IGNORE_NEXT_LINE_DIRECTIVE: &str = "<!-- urlsup-ignore-next-line -->";
+
 pub trait UrlFinder {
-    fn find_urls(&self, paths: Vec<&Path>) -> io::Result<Vec<UrlLocation>>;
+    fn find_urls(&self, paths: Vec<&Path>, opts: &UrlsUpOptions) -> io::Result<Vec<UrlLocation>>;
 }
 
 #[derive(Default)]
 pub struct Finder {}
 
 impl UrlFinder for Finder {
-    fn find_urls(&self, paths: Vec<&Path>) -> io::Result<Vec<UrlLocation>> {
+    fn find_urls(&self, paths: Vec<&Path>, opts: &UrlsUpOptions) -> io::Result<Vec<UrlLocation>> {
         let result = paths
             .into_iter()
             .flat_map(|path| {
-                // TODO: Don't panic here but instead let Error propagate in return Result
-                Finder::parse_lines_with_urls(path).unwrap_or_else(|_| {
-                    panic!(
-                        "Something went wrong parsing URL in file: {}",
-                        path.display()
-                    )
-                })
+                let url_locations = Finder::find_urls_in_file(path, opts);
+                match opts.show_context {
+                    true => Finder::add_context(path, url_locations),
+                    false => url_locations,
+                }
             })
-            .flat_map(Finder::parse_urls)
             .collect();
 
         Ok(result)
@@ -41,14 +74,109 @@ impl UrlFinder for Finder {
 type UrlMatch = (String, String, u64);
 
 impl Finder {
+    fn find_urls_in_file(path: &Path, opts: &UrlsUpOptions) -> Vec<UrlLocation> {
+        // Only check URLs in comments in source files when asked for
+        if let Some(url_locations) = Finder::parse_source_comment_urls(path, opts) {
+            return url_locations;
+        }
+
+        // Only check string values that are URLs in structured files
+        if let Some(url_locations) = Finder::parse_structured_urls(path, opts) {
+            return url_locations;
+        }
+
+        // Only check URLs in attributes in HTML files
+        if let Some(url_locations) = Finder::parse_html_urls(path, opts) {
+            return url_locations;
+        }
+
+        // Strip the markup around URLs in reStructuredText and AsciiDoc files
+        if let Some(url_locations) = Finder::parse_lightweight_markup_urls(path, opts) {
+            return url_locations;
+        }
+
+        // One unreadable file should not stop the other files from being checked
+        let url_matches = match Finder::parse_lines_with_urls(path) {
+            Ok(url_matches) => url_matches,
+            Err(e) => {
+                eprintln!(
+                    "> Warning: could not read URLs in file {}: {}",
+                    path.display(),
+                    e
+                );
+                vec![]
+            }
+        };
+
+        let url_matches = if opts.frontmatter_only {
+            Finder::apply_frontmatter_only(path, url_matches)
+        } else {
+            url_matches
+        };
+
+        let url_matches = if opts.inline_ignores {
+            Finder::apply_inline_ignores(url_matches)
+        } else {
+            url_matches
+        };
+
+        url_matches
+            .into_iter()
+            .flat_map(Finder::parse_urls)
+            .collect()
+    }
+
+    // Keeps the line every URL is on, read once per file
+    fn add_context(path: &Path, url_locations: Vec<UrlLocation>) -> Vec<UrlLocation> {
+        let content = match fs::read(path) {
+            Ok(content) => String::from_utf8_lossy(&content).into_owned(),
+            Err(_) => return url_locations,
+        };
+        let lines: Vec<&str> = content.lines().collect();
+
+        url_locations
+            .into_iter()
+            .map(|ul| UrlLocation {
+                context: (ul.line as usize)
+                    .checked_sub(1)
+                    .and_then(|index| lines.get(index))
+                    .map(|line| line.trim().to_string()),
+                ..ul
+            })
+            .collect()
+    }
+
+    // Reads one URL per line, skipping empty lines and # comments
+    pub fn read_url_list(&self, path: &Path) -> io::Result<Vec<UrlLocation>> {
+        let content = fs::read_to_string(path)?;
+        let url_locations = content
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(line_number, url)| UrlLocation {
+                url: url.to_string(),
+                line: line_number as u64,
+                file_name: path.display().to_string(),
+                allowed_statuses: vec![],
+                context: None,
+            })
+            .collect();
+
+        Ok(url_locations)
+    }
+
     fn parse_lines_with_urls(path: &Path) -> io::Result<Vec<UrlMatch>> {
-        let matcher = RegexMatcher::new(MARKDOWN_URL_PATTERN).unwrap();
+        // Also match lines with the next line directive since they usually don't contain a URL
+        let pattern = format!("{}|{}", MARKDOWN_URL_PATTERN, IGNORE_NEXT_LINE_DIRECTIVE);
+        let matcher = RegexMatcher::new(&pattern).unwrap();
 
         let mut matches = vec![];
+        // Searcher transcodes files with a UTF-16 BOM, and Lossy replaces invalid UTF-8
         Searcher::new().search_path(
             &matcher,
             &path,
-            UTF8(|line_number, line| {
+            Lossy(|line_number, line| {
                 let file_name = path.display().to_string();
                 let url_match: UrlMatch = (line.to_string(), file_name, line_number);
                 matches.push(url_match);
@@ -59,8 +187,515 @@ impl Finder {
         Ok(matches)
     }
 
+    // Keeps the URLs in the frontmatter of Markdown files, other files are left as they are
+    fn apply_frontmatter_only(path: &Path, url_matches: Vec<UrlMatch>) -> Vec<UrlMatch> {
+        let is_markdown = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| {
+                matches!(extension.to_lowercase().as_str(), "md" | "markdown")
+            });
+        if !is_markdown {
+            return url_matches;
+        }
+
+        let frontmatter_end = fs::read(path)
+            .ok()
+            .and_then(|content| Finder::frontmatter_end(&String::from_utf8_lossy(&content)))
+            .unwrap_or(0);
+
+        url_matches
+            .into_iter()
+            .filter(|(_, _, line_number)| *line_number < frontmatter_end)
+            .collect()
+    }
+
+    // Line number of the closing fence when the content starts with a frontmatter block
+    fn frontmatter_end(content: &str) -> Option<u64> {
+        let mut lines = content.lines().zip(1..);
+        match lines.next() {
+            Some((line, _)) if line.trim_end() == "---" => {}
+            _ => return None,
+        }
+        lines
+            .find(|(line, _)| matches!(line.trim_end(), "---" | "..."))
+            .map(|(_, line_number)| line_number)
+    }
+
+    // Same as the inline ignores for files read line by line, for files parsed as a whole
+    fn is_inline_ignored(lines: &[&str], line_number: u64) -> bool {
+        let index = line_number as usize - 1;
+        let is_ignored = lines
+            .get(index)
+            .is_some_and(|line| line.contains(IGNORE_DIRECTIVE));
+        let is_next_line_ignored =
+            index > 0 && lines[index - 1].contains(IGNORE_NEXT_LINE_DIRECTIVE);
+        is_ignored || is_next_line_ignored
+    }
+
+    fn apply_inline_ignores(url_matches: Vec<UrlMatch>) -> Vec<UrlMatch> {
+        let mut ignored_line = None;
+
+        url_matches
+            .into_iter()
+            .filter(|(line, _, line_number)| {
+                let is_ignored =
+                    line.contains(IGNORE_DIRECTIVE) || ignored_line == Some(*line_number);
+
+                if line.contains(IGNORE_NEXT_LINE_DIRECTIVE) {
+                    ignored_line = Some(line_number + 1);
+                }
+
+                !is_ignored
+            })
+            .collect()
+    }
+
+    // Returns None if the file isn't JSON or YAML, or if it can't be parsed as such
+    fn parse_structured_urls(path: &Path, opts: &UrlsUpOptions) -> Option<Vec<UrlLocation>> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        let content = String::from_utf8_lossy(&fs::read(path).ok()?).into_owned();
+
+        let mut values = vec![];
+        match extension.as_str() {
+            "json" => {
+                Finder::collect_json_strings(serde_json::from_str(&content).ok()?, &mut values)
+            }
+            "yaml" | "yml" => {
+                Finder::collect_yaml_strings(serde_yaml::from_str(&content).ok()?, &mut values)
+            }
+            _ => return None,
+        }
+
+        let mut urls: Vec<String> = values
+            .into_iter()
+            .filter(|value| Finder::is_absolute_url(value))
+            .collect();
+        urls.sort();
+        urls.dedup();
+
+        // Parsed values don't know their position so look them up in the file
+        let file_name = path.display().to_string();
+        let lines: Vec<&str> = content.lines().collect();
+        let unescaped_lines: Vec<String> = lines
+            .iter()
+            .map(|line| Finder::unescape_json(line))
+            .collect();
+        let mut url_locations: Vec<UrlLocation> = urls
+            .iter()
+            .flat_map(|url| {
+                let mut line_numbers: Vec<u64> = (1..)
+                    .zip(&lines)
+                    .filter(|(_, line)| Finder::line_contains_value(line, url))
+                    .map(|(line_number, _)| line_number)
+                    .collect();
+                // Escapes like \/ in JSON strings keep the value from being in the line as it is
+                if line_numbers.is_empty() {
+                    line_numbers = (1..)
+                        .zip(&unescaped_lines)
+                        .filter(|(_, line)| Finder::line_contains_value(line, url))
+                        .map(|(line_number, _)| line_number)
+                        .collect();
+                }
+                if line_numbers.is_empty() {
+                    line_numbers.push(1);
+                }
+
+                line_numbers
+                    .into_iter()
+                    .map(|line_number| UrlLocation {
+                        url: url.to_string(),
+                        line: line_number,
+                        file_name: file_name.to_owned(),
+                        allowed_statuses: lines
+                            .get(line_number as usize - 1)
+                            .map(|line| Finder::parse_allowed_statuses(line))
+                            .unwrap_or_default(),
+                        context: None,
+                    })
+                    .collect::<Vec<UrlLocation>>()
+            })
+            .filter(|ul| !opts.inline_ignores || !Finder::is_inline_ignored(&lines, ul.line))
+            .collect();
+        url_locations.sort_by(|a, b| (a.line, &a.url).cmp(&(b.line, &b.url)));
+
+        Some(url_locations)
+    }
+
+    // Returns None if the file isn't HTML
+    fn parse_html_urls(path: &Path, opts: &UrlsUpOptions) -> Option<Vec<UrlLocation>> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        if extension != "html" && extension != "htm" {
+            return None;
+        }
+
+        let content = String::from_utf8_lossy(&fs::read(path).ok()?).into_owned();
+        let markup = Finder::blank_out_non_markup(&content);
+        let lines: Vec<&str> = content.lines().collect();
+        let file_name = path.display().to_string();
+
+        let url_locations = HTML_URL_ATTRIBUTE_REGEX
+            .captures_iter(&markup)
+            .flat_map(|captures| {
+                let value = captures
+                    .iter()
+                    .skip(2)
+                    .flatten()
+                    .next()
+                    .map(|m| (m.start(), m.as_str()));
+                let (start, value) = match value {
+                    Some(value) => value,
+                    None => return vec![],
+                };
+                let line = markup[..start].matches('\n').count() as u64 + 1;
+                let allowed_statuses = Finder::parse_allowed_statuses(lines[line as usize - 1]);
+                let value = Finder::decode_html_entities(value);
+
+                // srcset holds comma separated "url descriptor" candidates, other attributes
+                // hold a single URL that can have commas in it
+                let urls: Vec<&str> = match captures[1].eq_ignore_ascii_case("srcset") {
+                    true => value
+                        .split(',')
+                        .filter_map(|candidate| candidate.split_whitespace().next())
+                        .collect(),
+                    false => vec![value.trim()],
+                };
+                urls.into_iter()
+                    .filter(|url| Finder::is_absolute_url(url))
+                    .map(|url| UrlLocation {
+                        url: url.to_string(),
+                        line,
+                        file_name: file_name.to_owned(),
+                        allowed_statuses: allowed_statuses.clone(),
+                        context: None,
+                    })
+                    .collect()
+            })
+            .filter(|ul| !opts.inline_ignores || !Finder::is_inline_ignored(&lines, ul.line))
+            .collect();
+
+        Some(url_locations)
+    }
+
+    // Returns None if the file isn't reStructuredText or AsciiDoc
+    fn parse_lightweight_markup_urls(
+        path: &Path,
+        opts: &UrlsUpOptions,
+    ) -> Option<Vec<UrlLocation>> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        if !matches!(extension.as_str(), "rst" | "adoc" | "asciidoc") {
+            return None;
+        }
+
+        let content = String::from_utf8_lossy(&fs::read(path).ok()?).into_owned();
+        let lines: Vec<&str> = content.lines().collect();
+        let file_name = path.display().to_string();
+
+        let url_locations = lines
+            .iter()
+            .zip(1..)
+            .filter(|(_, line_number)| {
+                !opts.inline_ignores || !Finder::is_inline_ignored(&lines, *line_number)
+            })
+            .flat_map(|(line, line_number)| {
+                let allowed_statuses = Finder::parse_allowed_statuses(line);
+                LIGHTWEIGHT_MARKUP_URL_REGEX
+                    .find_iter(line)
+                    .map(|m| Finder::trim_trailing_punctuation(m.as_str()))
+                    .filter(|url| Finder::is_absolute_url(url))
+                    .map(|url| UrlLocation {
+                        url: url.to_string(),
+                        line: line_number,
+                        file_name: file_name.to_owned(),
+                        allowed_statuses: allowed_statuses.clone(),
+                        context: None,
+                    })
+                    .collect::<Vec<UrlLocation>>()
+            })
+            .collect();
+
+        Some(url_locations)
+    }
+
+    // Returns None unless only URLs in comments are asked for and the file is Rust, Python,
+    // JavaScript or Go
+    fn parse_source_comment_urls(path: &Path, opts: &UrlsUpOptions) -> Option<Vec<UrlLocation>> {
+        if !opts.comments_only {
+            return None;
+        }
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        if !matches!(extension.as_str(), "rs" | "py" | "js" | "go") {
+            return None;
+        }
+
+        let content = String::from_utf8_lossy(&fs::read(path).ok()?).into_owned();
+        let file_name = path.display().to_string();
+        let url_matches: Vec<UrlMatch> = Finder::blank_out_non_comments(&content, &extension)
+            .lines()
+            .zip(1..)
+            .filter(|(line, _)| !line.trim().is_empty())
+            .map(|(line, line_number)| (line.to_string(), file_name.to_owned(), line_number))
+            .collect();
+
+        let url_matches = if opts.inline_ignores {
+            Finder::apply_inline_ignores(url_matches)
+        } else {
+            url_matches
+        };
+
+        Some(
+            url_matches
+                .into_iter()
+                .flat_map(Finder::parse_urls)
+                .collect(),
+        )
+    }
+
+    // Replaces everything but // and /* */ comments, or # comments in Python, with whitespace,
+    // keeping line breaks intact
+    fn blank_out_non_comments(content: &str, extension: &str) -> String {
+        let chars: Vec<char> = content.chars().collect();
+        let is_python = extension == "py";
+        let quotes: &[char] = match extension {
+            "rs" => &['"'],
+            "go" => &['"', '`'],
+            "py" => &['"', '\''],
+            _ => &['"', '\'', '`'],
+        };
+        let blank = |c: &char| if *c == '\n' { '\n' } else { ' ' };
+
+        let mut comments = String::with_capacity(content.len());
+        let mut i = 0;
+        while i < chars.len() {
+            let rest = &chars[i..];
+            let is_line_comment = match is_python {
+                true => rest[0] == '#',
+                false => rest.starts_with(&['/', '/']),
+            };
+            if is_line_comment {
+                let end = rest.iter().position(|&c| c == '\n').unwrap_or(rest.len());
+                comments.extend(&rest[..end]);
+                i += end;
+            } else if !is_python && rest.starts_with(&['/', '*']) {
+                let end = rest[2..]
+                    .windows(2)
+                    .position(|window| window == ['*', '/'])
+                    .map_or(rest.len(), |position| position + 4);
+                comments.extend(&rest[..end]);
+                i += end;
+            } else {
+                let follows_identifier =
+                    i > 0 && (chars[i - 1].is_alphanumeric() || chars[i - 1] == '_');
+                let end = Finder::literal_len(rest, extension, quotes, follows_identifier);
+                comments.extend(rest[..end.max(1)].iter().map(blank));
+                i += end.max(1);
+            }
+        }
+
+        comments
+    }
+
+    // Length of the string or char literal at the start, 0 when there is none
+    fn literal_len(
+        rest: &[char],
+        extension: &str,
+        quotes: &[char],
+        follows_identifier: bool,
+    ) -> usize {
+        let closing = |from: usize, delimiter: &[char], escapes: bool| {
+            let mut j = from;
+            while j < rest.len() {
+                if escapes && rest[j] == '\\' {
+                    j += 2;
+                } else if rest[j..].starts_with(delimiter) {
+                    return j + delimiter.len();
+                } else {
+                    j += 1;
+                }
+            }
+            rest.len()
+        };
+
+        match rest[0] {
+            // Raw strings like r#"..."# in Rust
+            'r' if extension == "rs" && !follows_identifier => {
+                let hashes = rest[1..].iter().take_while(|&&c| c == '#').count();
+                match rest.get(1 + hashes) {
+                    Some('"') => {
+                        let delimiter: Vec<char> =
+                            std::iter::once('"').chain(vec!['#'; hashes]).collect();
+                        closing(2 + hashes, &delimiter, false)
+                    }
+                    _ => 0,
+                }
+            }
+            // Char literals in Rust, a single quote can also start a lifetime
+            '\'' if extension == "rs" => match (rest.get(1), rest.get(2)) {
+                // The escaped char can be a quote itself
+                (Some('\\'), _) => closing(3, &['\''], false),
+                (Some(_), Some('\'')) => 3,
+                _ => 0,
+            },
+            quote if quotes.contains(&quote) => {
+                // Triple quoted strings in Python
+                if extension == "py" && rest.starts_with(&[quote, quote, quote]) {
+                    return closing(3, &[quote, quote, quote], true);
+                }
+                // Raw strings in Go
+                let escapes = !(extension == "go" && quote == '`');
+                closing(1, &[quote], escapes)
+            }
+            _ => 0,
+        }
+    }
+
+    // Punctuation ending a sentence is not part of the URL, but closing parentheses that have
+    // an opening one in the URL are
+    fn trim_trailing_punctuation(url: &str) -> &str {
+        let mut url = url;
+        loop {
+            let trimmed = url.trim_end_matches(['.', ',', ';', ':', '!', '?', '\'', '"']);
+            let trimmed = match trimmed.strip_suffix(')') {
+                Some(stripped) if trimmed.matches('(').count() < trimmed.matches(')').count() => {
+                    stripped
+                }
+                _ => trimmed,
+            };
+            if trimmed == url {
+                return url;
+            }
+            url = trimmed;
+        }
+    }
+
+    // Replaces comments and the contents of scripts and styles with whitespace, keeping line
+    // breaks intact
+    fn blank_out_non_markup(content: &str) -> String {
+        let blank = |s: &str| -> String {
+            s.chars()
+                .map(|c| if c == '\n' { '\n' } else { ' ' })
+                .collect()
+        };
+
+        HTML_NON_MARKUP_REGEX
+            .replace_all(content, |captures: &regex::Captures| {
+                match (
+                    captures.name("open"),
+                    captures.name("inner"),
+                    captures.name("close"),
+                ) {
+                    // Keep the tags since the opening tag can have a src attribute
+                    (Some(open), Some(inner), Some(close)) => format!(
+                        "{}{}{}",
+                        open.as_str(),
+                        blank(inner.as_str()),
+                        close.as_str()
+                    ),
+                    _ => blank(&captures[0]),
+                }
+            })
+            .to_string()
+    }
+
+    // Replaces character references like &amp; and &#38; with the characters they stand for
+    fn decode_html_entities(value: &str) -> String {
+        HTML_CHARACTER_REFERENCE_REGEX
+            .replace_all(value, |captures: &regex::Captures| {
+                let decoded = match (captures.get(1), captures.get(2), captures.get(3)) {
+                    (Some(decimal), _, _) => decimal.as_str().parse().ok().and_then(char::from_u32),
+                    (_, Some(hex), _) => u32::from_str_radix(hex.as_str(), 16)
+                        .ok()
+                        .and_then(char::from_u32),
+                    (_, _, Some(name)) => match name.as_str() {
+                        "amp" => Some('&'),
+                        "lt" => Some('<'),
+                        "gt" => Some('>'),
+                        "quot" => Some('"'),
+                        _ => Some('\''),
+                    },
+                    _ => None,
+                };
+                decoded.map_or_else(|| captures[0].to_string(), String::from)
+            })
+            .to_string()
+    }
+
+    fn collect_json_strings(value: serde_json::Value, strings: &mut Vec<String>) {
+        match value {
+            serde_json::Value::String(string) => strings.push(string),
+            serde_json::Value::Array(values) => values
+                .into_iter()
+                .for_each(|value| Finder::collect_json_strings(value, strings)),
+            serde_json::Value::Object(map) => map
+                .into_iter()
+                .for_each(|(_, value)| Finder::collect_json_strings(value, strings)),
+            _ => {}
+        }
+    }
+
+    fn collect_yaml_strings(value: serde_yaml::Value, strings: &mut Vec<String>) {
+        match value {
+            serde_yaml::Value::String(string) => strings.push(string),
+            serde_yaml::Value::Sequence(values) => values
+                .into_iter()
+                .for_each(|value| Finder::collect_yaml_strings(value, strings)),
+            serde_yaml::Value::Mapping(mapping) => mapping
+                .into_iter()
+                .for_each(|(_, value)| Finder::collect_yaml_strings(value, strings)),
+            _ => {}
+        }
+    }
+
+    fn is_absolute_url(value: &str) -> bool {
+        let is_http = value.starts_with("http://") || value.starts_with("https://");
+        // Skip templated placeholders like http://{host}/path
+        let is_template = value.contains(['{', '}', '<', '>']);
+
+        is_http
+            && !is_template
+            && reqwest::Url::parse(value)
+                .map(|url| url.host_str().is_some())
+                .unwrap_or(false)
+    }
+
+    // Replaces the \/ and \uXXXX escapes in JSON strings with the characters they stand for
+    fn unescape_json(line: &str) -> String {
+        let line = line.replace("\\/", "/");
+        JSON_UNICODE_ESCAPE_REGEX
+            .replace_all(&line, |captures: &regex::Captures| {
+                u32::from_str_radix(&captures[1], 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .map_or_else(|| captures[0].to_string(), String::from)
+            })
+            .to_string()
+    }
+
+    fn line_contains_value(line: &str, value: &str) -> bool {
+        line.match_indices(value).any(|(i, _)| {
+            // Make sure the whole value matched and not only the start of a longer URL
+            match line[i + value.len()..].chars().next() {
+                Some(c) => matches!(c, '"' | '\'' | ',' | ']' | '}') || c.is_whitespace(),
+                None => true,
+            }
+        })
+    }
+
+    fn parse_allowed_statuses(line: &str) -> Vec<u16> {
+        ALLOW_DIRECTIVE_REGEX
+            .captures_iter(line)
+            .flat_map(|captures| {
+                captures[1]
+                    .split(',')
+                    .filter_map(|s| s.trim().parse::<u16>().ok())
+                    .collect::<Vec<u16>>()
+            })
+            .collect()
+    }
+
     fn parse_urls(url_match: UrlMatch) -> Vec<UrlLocation> {
         let (url, file_name, line) = url_match;
+        let allowed_statuses = Finder::parse_allowed_statuses(&url);
 
         let mut finder = LinkFinder::new();
         finder.kinds(&[LinkKind::Url]);
@@ -71,6 +706,8 @@ impl Finder {
                 line,
                 file_name: file_name.to_owned(),
                 url: url.as_str().to_string(),
+                allowed_statuses: allowed_statuses.clone(),
+                context: None,
             })
             .collect()
     }
@@ -82,9 +719,472 @@ mod tests {
 
     use super::*;
     use std::io::Write;
+    use std::time::Duration;
 
     type TestResult = Result<(), Box<dyn std::error::Error>>;
 
+    fn opts(inline_ignores: bool) -> UrlsUpOptions {
+        UrlsUpOptions {
+            timeout: Duration::from_secs(10),
+            thread_count: 1,
+            inline_ignores,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_find_urls__skips_inline_ignored_urls() -> TestResult {
+        let mut file = tempfile::NamedTempFile::new()?;
+        let file_name = file.path().display().to_string();
+        file.write_all(
+            "arbitrary [something](http://keep.one) arbitrary\n\
+             arbitrary [something](http://ignore.one) <!-- urlsup-ignore -->\n\
+             <!-- urlsup-ignore-next-line -->\n\
+             arbitrary [something](http://ignore.two) arbitrary\n\
+             arbitrary [something](http://keep.two) arbitrary"
+                .as_bytes(),
+        )?;
+
+        let actual = Finder::default().find_urls(vec![file.path()], &opts(true))?;
+        let expected = vec![
+            UrlLocation {
+                url: "http://keep.one".to_string(),
+                line: 1,
+                file_name: file_name.to_string(),
+                allowed_statuses: vec![],
+                context: None,
+            },
+            UrlLocation {
+                url: "http://keep.two".to_string(),
+                line: 5,
+                file_name: file_name.to_string(),
+                allowed_statuses: vec![],
+                context: None,
+            },
+        ];
+
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_urls__when_inline_ignores_disabled() -> TestResult {
+        let mut file = tempfile::NamedTempFile::new()?;
+        file.write_all(
+            "arbitrary [something](http://ignore.one) <!-- urlsup-ignore -->\n\
+             <!-- urlsup-ignore-next-line -->\n\
+             arbitrary [something](http://ignore.two) arbitrary"
+                .as_bytes(),
+        )?;
+
+        let actual = Finder::default().find_urls(vec![file.path()], &opts(false))?;
+
+        assert_eq!(actual.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_urls__json_only_finds_url_values() -> TestResult {
+        let mut file = tempfile::Builder::new().suffix(".json").tempfile()?;
+        let file_name = file.path().display().to_string();
+        file.write_all(
+            r##"{
+  "homepage": "https://real.one",
+  "schema": { "$ref": "#/components/schemas/Pet" },
+  "example": "http://{host}/path",
+  "description": "See https://in-text.one for more",
+  "links": ["https://real.two", "https://real.one/longer"]
+}"##
+            .as_bytes(),
+        )?;
+
+        let actual = Finder::default().find_urls(vec![file.path()], &opts(true))?;
+        let expected = vec![
+            UrlLocation {
+                url: "https://real.one".to_string(),
+                line: 2,
+                file_name: file_name.to_string(),
+                allowed_statuses: vec![],
+                context: None,
+            },
+            UrlLocation {
+                url: "https://real.one/longer".to_string(),
+                line: 6,
+                file_name: file_name.to_string(),
+                allowed_statuses: vec![],
+                context: None,
+            },
+            UrlLocation {
+                url: "https://real.two".to_string(),
+                line: 6,
+                file_name: file_name.to_string(),
+                allowed_statuses: vec![],
+                context: None,
+            },
+        ];
+
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_urls__yaml_only_finds_url_values() -> TestResult {
+        let mut file = tempfile::Builder::new().suffix(".yml").tempfile()?;
+        let file_name = file.path().display().to_string();
+        file.write_all(
+            "name: arbitrary\n\
+             # https://in-comment.one\n\
+             url: https://real.one\n\
+             servers:\n  \
+               - url: 'http://{host}:8080'\n  \
+               - url: \"https://real.two\"\n"
+                .as_bytes(),
+        )?;
+
+        let actual = Finder::default().find_urls(vec![file.path()], &opts(true))?;
+        let expected = vec![
+            UrlLocation {
+                url: "https://real.one".to_string(),
+                line: 3,
+                file_name: file_name.to_string(),
+                allowed_statuses: vec![],
+                context: None,
+            },
+            UrlLocation {
+                url: "https://real.two".to_string(),
+                line: 6,
+                file_name: file_name.to_string(),
+                allowed_statuses: vec![],
+                context: None,
+            },
+        ];
+
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_urls__json_with_escapes_and_directives() -> TestResult {
+        let mut file = tempfile::Builder::new().suffix(".json").tempfile()?;
+        let file_name = file.path().display().to_string();
+        file.write_all(
+            r#"{
+  "escaped": "http:\/\/escaped.one\/path?a=1&b=2",
+  "ignored": ["https://ignored.one", "<!-- urlsup-ignore -->"],
+  "allowed": ["https://allowed.one", "<!-- urlsup-allow 403 -->"]
+}"#
+            .as_bytes(),
+        )?;
+
+        let actual = Finder::default().find_urls(vec![file.path()], &opts(true))?;
+        let expected = vec![
+            UrlLocation {
+                url: "http://escaped.one/path?a=1&b=2".to_string(),
+                line: 2,
+                file_name: file_name.to_string(),
+                allowed_statuses: vec![],
+                context: None,
+            },
+            UrlLocation {
+                url: "https://allowed.one".to_string(),
+                line: 4,
+                file_name: file_name.to_string(),
+                allowed_statuses: vec![403],
+                context: None,
+            },
+        ];
+
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_urls__html_only_finds_attribute_urls() -> TestResult {
+        let mut file = tempfile::Builder::new().suffix(".html").tempfile()?;
+        let file_name = file.path().display().to_string();
+        file.write_all(
+            r#"<html>
+<!-- <a href="https://in-comment.one">commented out</a> -->
+<script src="https://script-src.one"></script>
+<script>fetch("https://in-script.one");</script>
+<p>Visit https://in-text.one or <a href='https://link.one'>this</a></p>
+<img src=https://image.one srcset="https://small.one 1x, https://large.one 2x">
+<form action="https://form.one"></form>
+</html>"#
+                .as_bytes(),
+        )?;
+
+        let actual = Finder::default().find_urls(vec![file.path()], &opts(true))?;
+        let expected: Vec<UrlLocation> = vec![
+            ("https://script-src.one", 3),
+            ("https://link.one", 5),
+            ("https://image.one", 6),
+            ("https://small.one", 6),
+            ("https://large.one", 6),
+            ("https://form.one", 7),
+        ]
+        .into_iter()
+        .map(|(url, line)| UrlLocation {
+            url: url.to_string(),
+            line,
+            file_name: file_name.to_string(),
+            allowed_statuses: vec![],
+            context: None,
+        })
+        .collect();
+
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_urls__html_keeps_commas_and_decodes_entities() -> TestResult {
+        let mut file = tempfile::Builder::new().suffix(".html").tempfile()?;
+        file.write_all(
+            r#"<a href="http://127.0.0.1:1/maps?q=1,2">map</a>
+<a href="https://search.one/?a=1&amp;b=2&#38;c=3">search</a>
+<img srcset="https://small.one/?w=1&amp;h=1 1x,https://large.one 2x">"#
+                .as_bytes(),
+        )?;
+
+        let actual: Vec<String> = Finder::default()
+            .find_urls(vec![file.path()], &opts(true))?
+            .into_iter()
+            .map(|ul| ul.url)
+            .collect();
+        let expected = vec![
+            "http://127.0.0.1:1/maps?q=1,2",
+            "https://search.one/?a=1&b=2&c=3",
+            "https://small.one/?w=1&h=1",
+            "https://large.one",
+        ];
+
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_urls__frontmatter_only() -> TestResult {
+        let mut file = tempfile::Builder::new().suffix(".md").tempfile()?;
+        let file_name = file.path().display().to_string();
+        file.write_all(
+            "---\n\
+             title: Links\n\
+             canonical: https://frontmatter.com/page\n\
+             ---\n\
+             \n\
+             See https://body.com for more.\n"
+                .as_bytes(),
+        )?;
+        let frontmatter_only_opts = UrlsUpOptions {
+            frontmatter_only: true,
+            ..opts(true)
+        };
+
+        let actual = Finder::default().find_urls(vec![file.path()], &frontmatter_only_opts)?;
+        let expected = vec![UrlLocation {
+            url: "https://frontmatter.com/page".to_string(),
+            line: 3,
+            file_name: file_name.to_string(),
+            allowed_statuses: vec![],
+            context: None,
+        }];
+
+        assert_eq!(actual, expected);
+        assert_eq!(
+            Finder::default()
+                .find_urls(vec![file.path()], &opts(true))?
+                .len(),
+            2
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_urls__comments_only() -> TestResult {
+        let mut file = tempfile::Builder::new().suffix(".rs").tempfile()?;
+        file.write_all(
+            br##"// See https://line-comment.com/docs
+const ENDPOINT: &str = "https://string.com/api"; /* https://block-comment.com */
+const RAW: &str = r#"// https://raw-string.com "#;
+fn first<'a>(s: &'a str) -> char {
+    let _quotes = ['"', '\'']; // https://after-char.com
+    s.chars().next().unwrap()
+}
+"##,
+        )?;
+        let comments_only_opts = UrlsUpOptions {
+            comments_only: true,
+            ..opts(true)
+        };
+
+        let actual = Finder::default().find_urls(vec![file.path()], &comments_only_opts)?;
+        let actual: Vec<(&str, u64)> = actual.iter().map(|ul| (ul.url.as_str(), ul.line)).collect();
+
+        assert_eq!(
+            actual,
+            vec![
+                ("https://line-comment.com/docs", 1),
+                ("https://block-comment.com", 2),
+                ("https://after-char.com", 5),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_blank_out_non_comments__python_and_go() {
+        let python = "url = 'https://a.com'  # https://b.com\n\"\"\"\n# https://c.com\n\"\"\"\n";
+        let actual = Finder::blank_out_non_comments(python, "py");
+        assert_eq!(
+            actual.split_whitespace().collect::<Vec<&str>>(),
+            vec!["#", "https://b.com"]
+        );
+
+        let go = "u := `https://a.com // no`\n/* https://b.com\n*/ x := \"\\\" // no\"\n";
+        let actual = Finder::blank_out_non_comments(go, "go");
+        assert_eq!(
+            actual.split_whitespace().collect::<Vec<&str>>(),
+            vec!["/*", "https://b.com", "*/"]
+        );
+    }
+
+    #[test]
+    fn test_find_urls__show_context() -> TestResult {
+        let mut file = tempfile::NamedTempFile::new()?;
+        file.write_all(b"# Links\n\n  See https://context.com for more.  \n")?;
+        let show_context_opts = UrlsUpOptions {
+            show_context: true,
+            ..opts(true)
+        };
+
+        let actual = Finder::default().find_urls(vec![file.path()], &show_context_opts)?;
+
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].line, 3);
+        assert_eq!(
+            actual[0].context,
+            Some("See https://context.com for more.".to_string())
+        );
+        assert_eq!(
+            Finder::default().find_urls(vec![file.path()], &opts(true))?[0].context,
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_urls__rst_strips_markup() -> TestResult {
+        let mut file = tempfile::Builder::new().suffix(".rst").tempfile()?;
+        let file_name = file.path().display().to_string();
+        file.write_all(
+            "Title\n\
+             =====\n\
+             \n\
+             See `the docs <https://docs.rs/urlsup>`_ and `GitHub`_.\n\
+             \n\
+             .. _GitHub: https://github.com/simeg/urlsup\n\
+             A bare link https://en.wikipedia.org/wiki/Rust_(programming_language).\n"
+                .as_bytes(),
+        )?;
+
+        let actual = Finder::default().find_urls(vec![file.path()], &opts(true))?;
+        let expected = vec![
+            UrlLocation {
+                url: "https://docs.rs/urlsup".to_string(),
+                line: 4,
+                file_name: file_name.to_string(),
+                allowed_statuses: vec![],
+                context: None,
+            },
+            UrlLocation {
+                url: "https://github.com/simeg/urlsup".to_string(),
+                line: 6,
+                file_name: file_name.to_string(),
+                allowed_statuses: vec![],
+                context: None,
+            },
+            UrlLocation {
+                url: "https://en.wikipedia.org/wiki/Rust_(programming_language)".to_string(),
+                line: 7,
+                file_name: file_name.to_string(),
+                allowed_statuses: vec![],
+                context: None,
+            },
+        ];
+
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_urls__asciidoc_strips_markup() -> TestResult {
+        let mut file = tempfile::Builder::new().suffix(".adoc").tempfile()?;
+        let file_name = file.path().display().to_string();
+        file.write_all(
+            "= Title\n\
+             \n\
+             Read link:https://docs.rs/urlsup[the docs].\n\
+             Or https://github.com/simeg/urlsup[GitHub], or <https://crates.io>.\n"
+                .as_bytes(),
+        )?;
+
+        let actual = Finder::default().find_urls(vec![file.path()], &opts(true))?;
+        let expected = vec![
+            UrlLocation {
+                url: "https://docs.rs/urlsup".to_string(),
+                line: 3,
+                file_name: file_name.to_string(),
+                allowed_statuses: vec![],
+                context: None,
+            },
+            UrlLocation {
+                url: "https://github.com/simeg/urlsup".to_string(),
+                line: 4,
+                file_name: file_name.to_string(),
+                allowed_statuses: vec![],
+                context: None,
+            },
+            UrlLocation {
+                url: "https://crates.io".to_string(),
+                line: 4,
+                file_name: file_name.to_string(),
+                allowed_statuses: vec![],
+                context: None,
+            },
+        ];
+
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_urls__lightweight_markup_with_directives() -> TestResult {
+        let mut file = tempfile::Builder::new().suffix(".rst").tempfile()?;
+        let file_name = file.path().display().to_string();
+        file.write_all(
+            "See https://ignored.one <!-- urlsup-ignore -->\n\
+             .. <!-- urlsup-ignore-next-line -->\n\
+             See https://ignored.two\n\
+             See `the docs <https://allowed.one>`_ <!-- urlsup-allow 403,429 -->\n"
+                .as_bytes(),
+        )?;
+
+        let actual = Finder::default().find_urls(vec![file.path()], &opts(true))?;
+        let expected = vec![UrlLocation {
+            url: "https://allowed.one".to_string(),
+            line: 4,
+            file_name: file_name.to_string(),
+            allowed_statuses: vec![403, 429],
+            context: None,
+        }];
+        assert_eq!(actual, expected);
+
+        let actual = Finder::default().find_urls(vec![file.path()], &opts(false))?;
+        assert_eq!(actual.len(), 3);
+        Ok(())
+    }
+
     #[test]
     fn test_parse_urls() {
         let md_link =
@@ -96,11 +1196,15 @@ mod tests {
                 url: "http://foo.bar".to_string(),
                 line: 99,
                 file_name: "this-file-name".to_string(),
+                allowed_statuses: vec![],
+                context: None,
             },
             UrlLocation {
                 url: "http://foo2.bar".to_string(),
                 line: 99,
                 file_name: "this-file-name".to_string(),
+                allowed_statuses: vec![],
+                context: None,
             },
         ];
         let actual = Finder::parse_urls(url_match);
@@ -108,6 +1212,19 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_parse_urls__with_allow_directive() {
+        let md_link =
+            "arbitrary [something](http://foo.bar) <!-- urlsup-allow 403, 429 -->".to_string();
+        let url_match = (md_link, "this-file-name".to_string(), 99);
+
+        let actual = Finder::parse_urls(url_match);
+
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].url, "http://foo.bar");
+        assert_eq!(actual[0].allowed_statuses, vec![403, 429]);
+    }
+
     #[test]
     fn test_parse_urls__img_url() {
         let md_link = "arbitrary ![image](http://foo.bar) arbitrary".to_string();
@@ -117,6 +1234,8 @@ mod tests {
             url: "http://foo.bar".to_string(),
             line: 99,
             file_name: "this-file-name".to_string(),
+            allowed_statuses: vec![],
+            context: None,
         }];
         let actual = Finder::parse_urls(url_match);
 
@@ -132,6 +1251,8 @@ mod tests {
             url: "http://foo.bar".to_string(),
             line: 99,
             file_name: "this-file-name".to_string(),
+            allowed_statuses: vec![],
+            context: None,
         }];
         let actual = Finder::parse_urls(url_match);
 
@@ -193,6 +1314,73 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_lines_with_urls__from_utf16_file() -> TestResult {
+        let mut file = tempfile::NamedTempFile::new()?;
+        let mut bytes = vec![0xFF, 0xFE]; // UTF-16 LE BOM
+        for unit in "arbitrary http://specific-link.one arbitrary".encode_utf16() {
+            bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+        file.write_all(&bytes)?;
+
+        let actual = Finder::parse_lines_with_urls(file.path())?;
+        let actual_urls: Vec<UrlLocation> =
+            actual.into_iter().flat_map(Finder::parse_urls).collect();
+
+        assert_eq!(actual_urls.len(), 1);
+        assert_eq!(actual_urls[0].url, "http://specific-link.one");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_lines_with_urls__from_non_utf8_file() -> TestResult {
+        let mut file = tempfile::NamedTempFile::new()?;
+        // "café" encoded as Latin-1
+        file.write_all(b"caf\xe9 http://specific-link.one arbitrary\n")?;
+
+        let actual = Finder::parse_lines_with_urls(file.path())?;
+        let actual_urls: Vec<UrlLocation> =
+            actual.into_iter().flat_map(Finder::parse_urls).collect();
+
+        assert_eq!(actual_urls.len(), 1);
+        assert_eq!(actual_urls[0].url, "http://specific-link.one");
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_urls__html_from_non_utf8_file() -> TestResult {
+        let mut file = tempfile::Builder::new().suffix(".html").tempfile()?;
+        // "café" encoded as Latin-1
+        file.write_all(
+            b"<p>caf\xe9 <a href=\"https://link.one\">link</a></p>\n\
+              <script>fetch(\"https://in-script.one\");</script>\n",
+        )?;
+
+        let actual: Vec<String> = Finder::default()
+            .find_urls(vec![file.path()], &opts(true))?
+            .into_iter()
+            .map(|ul| ul.url)
+            .collect();
+
+        assert_eq!(actual, vec!["https://link.one"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_urls__continues_when_file_is_unreadable() -> TestResult {
+        let mut file = tempfile::NamedTempFile::new()?;
+        file.write_all("arbitrary http://specific-link.one arbitrary".as_bytes())?;
+
+        let actual = Finder::default().find_urls(
+            vec![Path::new("non_existing_file.txt"), file.path()],
+            &opts(true),
+        )?;
+
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].url, "http://specific-link.one");
+        Ok(())
+    }
+
     #[test]
     fn test_parse_lines_with_urls__from_file__when_non_existing_file() {
         let non_existing_file = "non_existing_file.txt";
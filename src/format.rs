@@ -0,0 +1,431 @@
+use crate::validator::{mask_credentials, Hop, ValidationResult};
+use crate::Report;
+use serde_json::json;
+
+pub fn markdown(report: &Report) -> String {
+    let broken_count = report.issues.len();
+    let success_rate = match report.checked_count {
+        0 => 100.0,
+        checked_count => {
+            (checked_count.saturating_sub(broken_count)) as f64 / checked_count as f64 * 100.0
+        }
+    };
+
+    let mut markdown = String::from("## urlsup report\n\n");
+    markdown.push_str("| Checked | Broken | Success rate |\n");
+    markdown.push_str("| ------- | ------ | ------------ |\n");
+    markdown.push_str(&format!(
+        "| {} | {} | {:.1}% |\n",
+        report.checked_count, broken_count, success_rate
+    ));
+
+    if report.issues.is_empty() {
+        markdown.push_str("\nNo issues!\n");
+    } else {
+        markdown.push_str(&table("Broken URLs", &report.issues));
+    }
+
+    if !report.redirects.is_empty() {
+        markdown.push_str(&redirects_table(&report.redirects));
+    }
+
+    if !report.successes.is_empty() {
+        markdown.push_str(&table("OK URLs", &report.successes));
+    }
+
+    markdown
+}
+
+// One JSON object per line for every result, followed by a summary object
+pub fn ndjson(report: &Report) -> String {
+    let results = report
+        .issues
+        .iter()
+        .map(|vr| (vr, false))
+        .chain(report.successes.iter().map(|vr| (vr, true)));
+
+    let mut ndjson = String::new();
+    for (vr, ok) in results {
+        let mut result = vr.to_json();
+        result["type"] = json!("result");
+        result["ok"] = json!(ok);
+        result["redirected_to"] = json!(vr.redirected_to);
+        ndjson.push_str(&format!("{}\n", result));
+    }
+
+    let summary = json!({
+        "type": "summary",
+        "checked_count": report.checked_count,
+        "broken_count": report.issues.len(),
+        "redirected_count": report.redirects.len(),
+        "max_runtime_exceeded": report.max_runtime_exceeded,
+        "max_requests_reached": report.max_requests_reached,
+        "interrupted": report.interrupted,
+    });
+    ndjson.push_str(&format!("{}\n", summary));
+
+    ndjson
+}
+
+// OK URLs that were redirected, with the URL they ended on
+pub fn redirects(redirects: &[ValidationResult]) -> String {
+    let mut text = format!("> Redirected ({})\n", redirects.len());
+    for (i, vr) in redirects.iter().enumerate() {
+        text.push_str(&format!(
+            "{:4}. {} -> {} - {} - L{}\n",
+            i + 1,
+            vr.url,
+            vr.redirected_to.as_deref().unwrap_or_default(),
+            vr.file_name,
+            vr.line
+        ));
+    }
+    text
+}
+
+// Number of checked URLs by status code
+pub fn stats(report: &Report) -> String {
+    let width = report
+        .status_counts
+        .keys()
+        .map(String::len)
+        .max()
+        .unwrap_or(0);
+    let mut stats = String::from("> Status codes\n");
+    for (status, count) in &report.status_counts {
+        stats.push_str(&format!("    {:width$}  {}\n", status, count));
+    }
+    stats
+}
+
+// Lists every URL once with the files and lines it is in beneath it, in the order of the results
+pub fn grouped_by_url(results: &[ValidationResult]) -> String {
+    let mut groups: Vec<(&ValidationResult, Vec<&ValidationResult>)> = vec![];
+    for vr in results {
+        match groups.iter_mut().find(|(first, _)| first.url == vr.url) {
+            Some((_, locations)) => locations.push(vr),
+            None => groups.push((vr, vec![vr])),
+        }
+    }
+
+    let mut grouped = String::new();
+    for (i, (first, locations)) in groups.iter().enumerate() {
+        match (&first.status_code, &first.description) {
+            (Some(status_code), _) => {
+                grouped.push_str(&format!("{:4}. {} - {}", i + 1, status_code, first.url))
+            }
+            _ => grouped.push_str(&format!("{:4}. {} - {}", i + 1, first.url, status(first))),
+        }
+        if let Some(suggestion) = &first.suggestion {
+            grouped.push_str(&format!(" - did you mean {}?", suggestion));
+        }
+        grouped.push('\n');
+
+        for vr in locations {
+            grouped.push_str(&format!("        {} - L{}", vr.file_name, vr.line));
+            if vr.occurrences > 1 {
+                grouped.push_str(&format!(" ({} times)", vr.occurrences));
+            }
+            grouped.push('\n');
+            if let Some(context) = &vr.context {
+                grouped.push_str(&format!("          {}\n", context));
+            }
+        }
+    }
+
+    grouped
+}
+
+// Every response on the way to the result of a single URL, and whether it is an issue
+pub fn trace(hops: &[Hop], vr: &ValidationResult, allowed_by: &[&str]) -> String {
+    let mut trace = String::new();
+    if !hops.is_empty() {
+        trace.push_str("> Responses\n");
+    }
+    for (i, hop) in hops.iter().enumerate() {
+        trace.push_str(&format!("{:4}. {}\n", i + 1, mask_credentials(&hop.url)));
+        if let Some(remote_addr) = hop.remote_addr {
+            trace.push_str(&format!("      Address: {}\n", remote_addr));
+        }
+        trace.push_str(&format!("      Status: {}\n", hop.status_code));
+        trace.push_str(&format!(
+            "      Time (ms): {}\n",
+            hop.response_time.as_millis()
+        ));
+        trace.push_str("      Headers:\n");
+        for (name, value) in &hop.headers {
+            trace.push_str(&format!(
+                "        {}: {}\n",
+                name,
+                String::from_utf8_lossy(value.as_bytes())
+            ));
+        }
+    }
+
+    trace.push_str(&format!("> Result: {}\n", status(vr)));
+    if vr.is_ok() {
+        trace.push_str("> OK\n");
+    } else if allowed_by.is_empty() {
+        trace.push_str("> Issue\n");
+    } else {
+        trace.push_str(&format!("> Allowed by: {}\n", allowed_by.join(", ")));
+    }
+
+    trace
+}
+
+// Rows are in the order of the results
+fn table(title: &str, results: &[ValidationResult]) -> String {
+    let mut table = format!("\n### {}\n\n", title);
+    table.push_str("| URL | File | Line | Status |\n");
+    table.push_str("| --- | ---- | ---- | ------ |\n");
+    for vr in results {
+        table.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            escape(&vr.url),
+            escape(&vr.file_name),
+            vr.line,
+            escape(&status(vr))
+        ));
+    }
+
+    table
+}
+
+fn redirects_table(redirects: &[ValidationResult]) -> String {
+    let mut table = format!("\n### Redirected URLs ({})\n\n", redirects.len());
+    table.push_str("| URL | Redirected to | File | Line |\n");
+    table.push_str("| --- | ------------- | ---- | ---- |\n");
+    for vr in redirects {
+        table.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            escape(&vr.url),
+            escape(vr.redirected_to.as_deref().unwrap_or_default()),
+            escape(&vr.file_name),
+            vr.line
+        ));
+    }
+
+    table
+}
+
+fn status(vr: &ValidationResult) -> String {
+    match (&vr.status_code, &vr.description) {
+        (Some(status_code), _) => status_code.to_string(),
+        (None, Some(description)) => description.to_string(),
+        (None, None) => "unknown".to_string(),
+    }
+}
+
+// Pipes would otherwise end the table cell early
+fn escape(value: &str) -> String {
+    value.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn validation_result(
+        url: &str,
+        file_name: &str,
+        line: u64,
+        status_code: u16,
+    ) -> ValidationResult {
+        ValidationResult {
+            url: url.to_string(),
+            line,
+            file_name: file_name.to_string(),
+            status_code: Some(status_code),
+            description: None,
+            allowed_statuses: vec![],
+            response_time: None,
+            occurrences: 1,
+            suggestion: None,
+            context: None,
+            redirected_to: None,
+        }
+    }
+
+    #[test]
+    fn test_markdown__has_summary_and_row_per_issue() {
+        let report = Report {
+            checked_count: 4,
+            issues: vec![
+                validation_result("http://b.com/?a|b", "b.md", 3, 404),
+                validation_result("http://a.com", "a.md", 7, 500),
+            ],
+            max_runtime_exceeded: false,
+            interrupted: false,
+            successes: vec![],
+            redirects: vec![],
+            status_counts: BTreeMap::new(),
+            max_requests_reached: false,
+        };
+
+        let actual = markdown(&report);
+
+        assert!(actual.contains("| Checked | Broken | Success rate |\n"));
+        assert!(actual.contains("| 4 | 2 | 50.0% |\n"));
+        assert!(actual.contains("| URL | File | Line | Status |\n"));
+        assert!(actual.ends_with(
+            "| http://b.com/?a\\|b | b.md | 3 | 404 |\n| http://a.com | a.md | 7 | 500 |\n"
+        ));
+    }
+
+    #[test]
+    fn test_redirects__in_text_markdown_and_ndjson() {
+        let mut redirected = validation_result("http://a.com", "a.md", 3, 200);
+        redirected.redirected_to = Some("https://a.com/".to_string());
+        let report = Report {
+            checked_count: 1,
+            issues: vec![],
+            max_runtime_exceeded: false,
+            interrupted: false,
+            successes: vec![],
+            redirects: vec![redirected],
+            status_counts: BTreeMap::new(),
+            max_requests_reached: false,
+        };
+
+        assert_eq!(
+            redirects(&report.redirects),
+            "> Redirected (1)\n   1. http://a.com -> https://a.com/ - a.md - L3\n"
+        );
+        assert!(markdown(&report).ends_with(
+            "### Redirected URLs (1)\n\n| URL | Redirected to | File | Line |\n| --- | ------------- | ---- | ---- |\n| http://a.com | https://a.com/ | a.md | 3 |\n"
+        ));
+        assert!(ndjson(&report).contains("\"redirected_count\":1"));
+    }
+
+    #[test]
+    fn test_markdown__with_successes() {
+        let report = Report {
+            checked_count: 2,
+            issues: vec![validation_result("http://broken.com", "a.md", 1, 404)],
+            max_runtime_exceeded: false,
+            interrupted: false,
+            successes: vec![validation_result("http://ok.com", "a.md", 2, 200)],
+            redirects: vec![],
+            status_counts: BTreeMap::new(),
+            max_requests_reached: false,
+        };
+
+        let actual = markdown(&report);
+
+        assert!(actual.contains("### Broken URLs\n"));
+        assert!(actual.contains("| http://broken.com | a.md | 1 | 404 |\n"));
+        assert!(actual.ends_with("### OK URLs\n\n| URL | File | Line | Status |\n| --- | ---- | ---- | ------ |\n| http://ok.com | a.md | 2 | 200 |\n"));
+    }
+
+    #[test]
+    fn test_ndjson__line_per_result_and_summary() {
+        let report = Report {
+            checked_count: 3,
+            issues: vec![
+                validation_result("http://a.com/\"quoted\"", "a.md", 3, 404),
+                validation_result("http://b.com", "b\nmd", 7, 500),
+            ],
+            max_runtime_exceeded: false,
+            interrupted: false,
+            successes: vec![validation_result("http://ok.com", "a.md", 1, 200)],
+            redirects: vec![],
+            status_counts: BTreeMap::new(),
+            max_requests_reached: false,
+        };
+
+        let actual = ndjson(&report);
+        let lines: Vec<serde_json::Value> = actual
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert!(actual.ends_with('\n'));
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0]["type"], "result");
+        assert_eq!(lines[0]["url"], "http://a.com/\"quoted\"");
+        assert_eq!(lines[0]["status_code"], 404);
+        assert_eq!(lines[0]["error_code"], "HTTP_4XX");
+        assert_eq!(lines[0]["ok"], false);
+        assert_eq!(lines[1]["file_name"], "b\nmd");
+        assert_eq!(lines[2]["url"], "http://ok.com");
+        assert_eq!(lines[2]["ok"], true);
+        assert_eq!(lines[3]["type"], "summary");
+        assert_eq!(lines[3]["checked_count"], 3);
+        assert_eq!(lines[3]["broken_count"], 2);
+        assert_eq!(lines[3]["max_requests_reached"], false);
+        assert_eq!(lines[3]["interrupted"], false);
+    }
+
+    #[test]
+    fn test_grouped_by_url__one_entry_per_url_with_all_locations() {
+        let results = vec![
+            validation_result("http://a.com", "a.md", 3, 404),
+            validation_result("http://b.com", "a.md", 5, 500),
+            validation_result("http://a.com", "b.md", 7, 404),
+            validation_result("http://a.com", "c.md", 1, 404),
+        ];
+
+        let actual = grouped_by_url(&results);
+
+        assert_eq!(
+            actual,
+            concat!(
+                "   1. 404 - http://a.com\n",
+                "        a.md - L3\n",
+                "        b.md - L7\n",
+                "        c.md - L1\n",
+                "   2. 500 - http://b.com\n",
+                "        a.md - L5\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_stats__aligned_count_per_status() {
+        let report = Report {
+            checked_count: 6,
+            issues: vec![],
+            max_runtime_exceeded: false,
+            interrupted: false,
+            successes: vec![],
+            redirects: vec![],
+            status_counts: BTreeMap::from([
+                ("200".to_string(), 3),
+                ("404".to_string(), 2),
+                ("TIMEOUT".to_string(), 1),
+            ]),
+            max_requests_reached: false,
+        };
+
+        let actual = stats(&report);
+
+        assert_eq!(
+            actual,
+            "> Status codes\n    200      3\n    404      2\n    TIMEOUT  1\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown__no_issues() {
+        let report = Report {
+            checked_count: 0,
+            issues: vec![],
+            max_runtime_exceeded: false,
+            interrupted: false,
+            successes: vec![],
+            redirects: vec![],
+            status_counts: BTreeMap::new(),
+            max_requests_reached: false,
+        };
+
+        let actual = markdown(&report);
+
+        assert!(actual.contains("| 0 | 0 | 100.0% |\n"));
+        assert!(actual.ends_with("No issues!\n"));
+        assert!(!actual.contains("| URL | File | Line | Status |"));
+    }
+}
@@ -0,0 +1,86 @@
+use regex::Regex;
+
+// Name of the file with URL patterns to never report, looked up in the current directory
+pub const IGNORE_FILE: &str = ".urlsupignore";
+
+// Lines starting with this are regexes, other lines are globs
+const REGEX_PREFIX: &str = "regex:";
+
+// Parses one pattern per line into regexes, skipping empty lines and # comments
+pub fn parse(content: &str) -> Result<Vec<Regex>, regex::Error> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| match line.strip_prefix(REGEX_PREFIX) {
+            Some(pattern) => Regex::new(pattern.trim()),
+            None => Regex::new(&glob_to_regex(line)),
+        })
+        .collect()
+}
+
+// Globs match the whole URL, * matches any characters and ? matches one
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    fn is_ignored(patterns: &[Regex], url: &str) -> bool {
+        patterns.iter().any(|re| re.is_match(url))
+    }
+
+    #[test]
+    fn test_parse__glob_entries() {
+        let patterns = parse("https://*.example.com/*\nhttp://localhost:?000/").unwrap();
+
+        assert_eq!(patterns.len(), 2);
+        assert!(is_ignored(&patterns, "https://docs.example.com/a/b"));
+        assert!(is_ignored(&patterns, "http://localhost:3000/"));
+        assert!(!is_ignored(&patterns, "https://example.com/a"));
+        assert!(!is_ignored(&patterns, "http://localhost:3000/a"));
+        // Dots in globs are not wildcards
+        assert!(!is_ignored(&patterns, "https://docs.exampleXcom/a"));
+    }
+
+    #[test]
+    fn test_parse__regex_entries() {
+        let patterns = parse("regex: ^https://api\\.example\\.com/v[0-9]+/").unwrap();
+
+        assert_eq!(patterns.len(), 1);
+        assert!(is_ignored(&patterns, "https://api.example.com/v2/users"));
+        assert!(!is_ignored(
+            &patterns,
+            "https://api.example.com/latest/users"
+        ));
+    }
+
+    #[test]
+    fn test_parse__skips_comments_and_empty_lines() {
+        let content = "# Local URLs\n\n   \n  # Indented comment\nhttp://localhost/*\n";
+
+        let patterns = parse(content).unwrap();
+
+        assert_eq!(patterns.len(), 1);
+        assert!(is_ignored(&patterns, "http://localhost/a"));
+        assert!(!is_ignored(&patterns, "# Local URLs"));
+    }
+
+    #[test]
+    fn test_parse__invalid_regex() {
+        assert!(parse("regex: (unclosed").is_err());
+    }
+}
@@ -0,0 +1,117 @@
+use crate::resume;
+use crate::validator::{mask_credentials, ValidationResult};
+use crate::UrlLocation;
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// OK results of the last run for the given URLs, at where the URLs are now, none when there
+// was no last run
+pub fn ok_results(path: &Path, urls: &[UrlLocation]) -> io::Result<Vec<ValidationResult>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+    // The URLs are saved with the credentials masked
+    let urls: HashMap<String, &UrlLocation> = urls
+        .iter()
+        .map(|ul| (mask_credentials(&ul.url), ul))
+        .collect();
+
+    let results = content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .filter_map(|value| resume::from_json(&value))
+        .filter(ValidationResult::is_ok)
+        .filter_map(|vr| {
+            let ul = urls.get(&vr.url)?;
+            Some(ValidationResult {
+                url: ul.url.clone(),
+                line: ul.line,
+                file_name: ul.file_name.clone(),
+                allowed_statuses: ul.allowed_statuses.clone(),
+                context: ul.context.clone(),
+                ..vr
+            })
+        })
+        .collect();
+    Ok(results)
+}
+
+// Saves the results of this run for the next one
+pub fn save(path: &Path, results: &[ValidationResult]) -> io::Result<()> {
+    let content: String = results
+        .iter()
+        .map(|vr| format!("{}\n", resume::to_json(vr)))
+        .collect();
+    fs::write(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use std::time::Duration;
+
+    type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+    fn validation_result(url: &str, status_code: u16) -> ValidationResult {
+        ValidationResult {
+            url: url.to_string(),
+            line: 1,
+            file_name: "old.md".to_string(),
+            status_code: Some(status_code),
+            description: None,
+            allowed_statuses: vec![],
+            response_time: Some(Duration::from_millis(10)),
+            occurrences: 1,
+            suggestion: None,
+            context: None,
+            redirected_to: None,
+        }
+    }
+
+    #[test]
+    fn test_ok_results__only_ok_urls_that_are_still_found() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("last-run");
+        save(
+            &path,
+            &[
+                validation_result("http://ok.com", 200),
+                validation_result("http://broken.com", 404),
+                validation_result("http://gone.com", 200),
+            ],
+        )?;
+        let urls = vec![
+            UrlLocation {
+                url: "http://ok.com".to_string(),
+                line: 7,
+                file_name: "new.md".to_string(),
+                allowed_statuses: vec![],
+                context: None,
+            },
+            UrlLocation {
+                url: "http://broken.com".to_string(),
+                line: 8,
+                file_name: "new.md".to_string(),
+                allowed_statuses: vec![],
+                context: None,
+            },
+        ];
+
+        let actual = ok_results(&path, &urls)?;
+
+        assert_eq!(actual, vec![validation_result("http://ok.com", 200)]);
+        assert_eq!(
+            (actual[0].file_name.as_str(), actual[0].line),
+            ("new.md", 7)
+        );
+        assert!(ok_results(&dir.path().join("missing"), &urls)?.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,205 @@
+use serde_json::{json, Value};
+
+use crate::sink::ResultSink;
+use crate::validator::{mask_credentials, ValidationResult};
+use crate::UrlLocation;
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+
+// Loads the results saved by an earlier run of the same URLs and opens the state file to save
+// the next results to, starting over when the state file is missing or for other URLs
+pub fn open(path: &Path, urls: &[UrlLocation]) -> io::Result<(Vec<ValidationResult>, File)> {
+    let hash = urls_hash(urls);
+    let results = match fs::read_to_string(path) {
+        Ok(content) => {
+            let results = parse(&content, &hash);
+            if results.is_none() {
+                eprintln!(
+                    "> Warning: state file {} is for other URLs, starting over",
+                    path.display()
+                );
+            }
+            results
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e),
+    };
+
+    match results {
+        Some(results) => Ok((
+            restore_urls(results, urls),
+            OpenOptions::new().append(true).open(path)?,
+        )),
+        None => {
+            let mut file = File::create(path)?;
+            writeln!(file, "{}", json!({ "urls_hash": hash }))?;
+            Ok((vec![], file))
+        }
+    }
+}
+
+// Saves every result to the state file before passing it on, with the credentials masked
+pub struct StateSink<'a> {
+    pub inner: &'a mut dyn ResultSink,
+    pub file: Option<File>,
+}
+
+impl ResultSink for StateSink<'_> {
+    fn on_result(&mut self, result: &ValidationResult) {
+        let result = result.masked();
+        if let Some(file) = &mut self.file {
+            // Losing a result only means checking the URL again when resuming
+            if let Err(e) = writeln!(file, "{}", to_json(&result)) {
+                eprintln!("> Warning: could not save result to state file: {}", e);
+            }
+        }
+        self.inner.on_result(&result);
+    }
+}
+
+// Puts back the credentials that were masked when saving, from the URLs found in the files
+fn restore_urls(results: Vec<ValidationResult>, urls: &[UrlLocation]) -> Vec<ValidationResult> {
+    let urls: HashMap<String, &str> = urls
+        .iter()
+        .map(|ul| (mask_credentials(&ul.url), ul.url.as_str()))
+        .collect();
+    results
+        .into_iter()
+        .map(|vr| match urls.get(&vr.url) {
+            Some(url) => ValidationResult {
+                url: url.to_string(),
+                ..vr
+            },
+            None => vr,
+        })
+        .collect()
+}
+
+// None when the state file is for other URLs
+fn parse(content: &str, hash: &str) -> Option<Vec<ValidationResult>> {
+    let mut lines = content.lines();
+    let header: Value = serde_json::from_str(lines.next()?).ok()?;
+    if header["urls_hash"] != hash {
+        return None;
+    }
+
+    // The last line is cut off when the run was killed while saving it
+    let results = lines
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .filter_map(|value| from_json(&value))
+        .collect();
+    Some(results)
+}
+
+// State files end up in caches and build artifacts, so the credentials are masked
+pub fn to_json(vr: &ValidationResult) -> Value {
+    let vr = vr.masked();
+    let mut value = vr.to_json();
+    value["allowed_statuses"] = json!(vr.allowed_statuses);
+    value["response_time_ms"] = json!(vr.response_time.map(|time| time.as_millis() as u64));
+    value["context"] = json!(vr.context);
+    value["redirected_to"] = json!(vr.redirected_to);
+    value
+}
+
+pub fn from_json(value: &Value) -> Option<ValidationResult> {
+    Some(ValidationResult {
+        url: value["url"].as_str()?.to_string(),
+        line: value["line"].as_u64()?,
+        file_name: value["file_name"].as_str()?.to_string(),
+        status_code: value["status_code"]
+            .as_u64()
+            .map(|status_code| status_code as u16),
+        description: value["description"].as_str().map(String::from),
+        allowed_statuses: value["allowed_statuses"]
+            .as_array()?
+            .iter()
+            .filter_map(|status_code| status_code.as_u64())
+            .map(|status_code| status_code as u16)
+            .collect(),
+        response_time: value["response_time_ms"]
+            .as_u64()
+            .map(Duration::from_millis),
+        occurrences: 1,
+        suggestion: None,
+        context: value["context"].as_str().map(String::from),
+        redirected_to: value["redirected_to"].as_str().map(String::from),
+    })
+}
+
+// FNV-1a hash of the sorted URLs, which unlike the std hasher is the same for every build
+fn urls_hash(urls: &[UrlLocation]) -> String {
+    let mut urls: Vec<&str> = urls.iter().map(|ul| ul.url.as_str()).collect();
+    urls.sort_unstable();
+
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in urls.join("\n").bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    fn url_location(url: &str) -> UrlLocation {
+        UrlLocation {
+            url: url.to_string(),
+            line: 99,                           // arbitrary
+            file_name: "arbitrary".to_string(), // arbitrary
+            allowed_statuses: vec![],
+            context: None,
+        }
+    }
+
+    #[test]
+    fn test_urls_hash__ignores_order() {
+        let urls = vec![url_location("http://a.com"), url_location("http://b.com")];
+        let reversed = vec![url_location("http://b.com"), url_location("http://a.com")];
+        let other = vec![url_location("http://a.com"), url_location("http://c.com")];
+
+        assert_eq!(urls_hash(&urls), urls_hash(&reversed));
+        assert_ne!(urls_hash(&urls), urls_hash(&other));
+    }
+
+    #[test]
+    fn test_parse__round_trips_results_and_skips_cut_off_line() {
+        let vr = ValidationResult {
+            url: "http://a.com".to_string(),
+            line: 3,
+            file_name: "a.md".to_string(),
+            status_code: Some(429),
+            description: None,
+            allowed_statuses: vec![429],
+            response_time: Some(Duration::from_millis(120)),
+            occurrences: 1,
+            suggestion: None,
+            context: Some("See http://a.com".to_string()),
+            redirected_to: Some("https://a.com/".to_string()),
+        };
+        let content = format!(
+            "{}\n{}\n{{\"url\": \"http://b.c",
+            json!({ "urls_hash": "abc" }),
+            to_json(&vr)
+        );
+
+        let actual = parse(&content, "abc").unwrap();
+
+        assert_eq!(actual, vec![vr.clone()]);
+        assert_eq!(actual[0].line, 3);
+        assert_eq!(actual[0].file_name, "a.md");
+        assert_eq!(actual[0].allowed_statuses, vec![429]);
+        assert_eq!(actual[0].response_time, Some(Duration::from_millis(120)));
+        assert_eq!(actual[0].context, vr.context);
+        assert_eq!(actual[0].redirected_to, vr.redirected_to);
+        assert!(parse(&content, "other").is_none());
+    }
+}
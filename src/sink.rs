@@ -0,0 +1,17 @@
+use crate::validator::ValidationResult;
+use crate::Report;
+
+// Receives results while URLs are being checked, e.g. to show them as they arrive
+pub trait ResultSink: Send {
+    // Called for every checked URL, including the OK ones and the allowed ones, with the
+    // credentials in the URLs masked
+    fn on_result(&mut self, _result: &ValidationResult) {}
+
+    // Called once all URLs are checked with the URLs that have issues
+    fn on_finish(&mut self, _report: &Report) {}
+}
+
+// Ignores all results
+pub struct NullSink;
+
+impl ResultSink for NullSink {}
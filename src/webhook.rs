@@ -0,0 +1,116 @@
+use reqwest::header::CONTENT_TYPE;
+use serde_json::{json, Value};
+
+use crate::validator::ValidationResult;
+
+pub async fn notify(
+    client: &reqwest::Client,
+    webhook_url: &str,
+    url_count: usize,
+    results: &[ValidationResult],
+    max_urls: usize,
+) -> reqwest::Result<()> {
+    client
+        .post(webhook_url)
+        .header(CONTENT_TYPE, "application/json")
+        .body(payload(url_count, results, max_urls).to_string())
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(())
+}
+
+fn payload(url_count: usize, results: &[ValidationResult], max_urls: usize) -> Value {
+    let broken_urls: Vec<Value> = results
+        .iter()
+        .take(max_urls)
+        .map(ValidationResult::to_json)
+        .collect();
+
+    json!({
+        // Slack and Teams display the "text" field as the message
+        "text": format!("urlsup found {} broken URL(s)", results.len()),
+        "checked_count": url_count,
+        "broken_count": results.len(),
+        "truncated": results.len() > max_urls,
+        "broken_urls": broken_urls,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use crate::validator::build_client;
+    use crate::UrlsUpOptions;
+    use mockito::{mock, Matcher};
+    use std::time::Duration;
+
+    fn validation_result(url: &str, status_code: Option<u16>) -> ValidationResult {
+        ValidationResult {
+            url: url.to_string(),
+            line: 0, // arbitrary
+            file_name: "arbitrary".to_string(),
+            status_code,
+            description: None,
+            allowed_statuses: vec![],
+            response_time: None,
+            occurrences: 1,
+            suggestion: None,
+            context: None,
+            redirected_to: None,
+        }
+    }
+
+    #[test]
+    fn test_payload__truncates_broken_urls() {
+        let results = vec![
+            validation_result("http://first.com", Some(404)),
+            validation_result("http://second.com", Some(500)),
+        ];
+
+        let actual = payload(10, &results, 1);
+
+        assert_eq!(actual["checked_count"], 10);
+        assert_eq!(actual["broken_count"], 2);
+        assert_eq!(actual["truncated"], true);
+        assert_eq!(actual["broken_urls"].as_array().unwrap().len(), 1);
+        assert_eq!(actual["broken_urls"][0]["url"], "http://first.com");
+    }
+
+    #[tokio::test]
+    async fn test_notify__posts_summary() -> Result<(), Box<dyn std::error::Error>> {
+        let opts = UrlsUpOptions {
+            timeout: Duration::from_secs(10),
+            thread_count: 1,
+            ..Default::default()
+        };
+        let m = mock("POST", "/webhook")
+            .match_header("content-type", "application/json")
+            .match_body(Matcher::Json(json!({
+                "text": "urlsup found 1 broken URL(s)",
+                "checked_count": 3,
+                "broken_count": 1,
+                "truncated": false,
+                "broken_urls": [{
+                    "url": "http://broken.com",
+                    "file_name": "arbitrary",
+                    "line": 0,
+                    "status_code": 404,
+                    "description": null,
+                    "error_code": "HTTP_4XX",
+                }],
+            })))
+            .with_status(200)
+            .create();
+        let endpoint = mockito::server_url() + "/webhook";
+        let results = vec![validation_result("http://broken.com", Some(404))];
+
+        notify(&build_client(&opts), &endpoint, 3, &results, 50).await?;
+
+        m.assert();
+        Ok(())
+    }
+}